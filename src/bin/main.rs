@@ -4,9 +4,13 @@ use sys_metrics::{cpu::*, disks::*, host::*, memory::*, network::*, virt::*};
 fn main() {
     dbg!(get_logical_count());
     dbg!(get_physical_count());
+    #[cfg(target_os = "linux")]
+    dbg!(get_effective_cpu_count());
     dbg!(get_cpufreq());
     dbg!(get_cpustats());
     dbg!(get_cputimes());
+    #[cfg(target_os = "linux")]
+    dbg!(get_cputimes_percpu());
     dbg!(get_loadavg());
     dbg!(get_physical_ioblocks());
     dbg!(get_partitions_physical());
@@ -17,8 +21,12 @@ fn main() {
     dbg!(get_users());
     dbg!(get_uuid());
     dbg!(get_memory());
+    #[cfg(target_os = "linux")]
+    dbg!(get_effective_memory());
     dbg!(get_swap());
     dbg!(has_swap());
     dbg!(get_physical_ionets());
+    #[cfg(target_os = "linux")]
+    dbg!(get_interfaces());
     dbg!(get_virt_info());
 }
@@ -0,0 +1,108 @@
+mod sys;
+
+pub use sys::*;
+
+use serde::Serialize;
+
+/// Cgroup hierarchies mounted on the system.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum CgroupMode {
+    /// Only the legacy (v1) hierarchies
+    V1,
+    /// Only the unified (v2) hierarchy
+    V2,
+    /// The v1 hierarchies with the unified one (without controllers) alongside
+    Hybrid,
+}
+
+/// Struct containing the cpu accounting of a cgroup (cpu.stat and cpu.max).
+///
+/// All times are in microseconds.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CgroupCpu {
+    pub usage_usec: u64,
+    pub user_usec: u64,
+    pub system_usec: u64,
+    /// Only present when the cpu controller is enabled
+    pub nr_periods: Option<u64>,
+    pub nr_throttled: Option<u64>,
+    pub throttled_usec: Option<u64>,
+    /// None if there's no cpu.max (root cgroup or cpu controller disabled)
+    pub max: Option<CpuMax>,
+}
+
+/// Struct containing the cpu bandwidth limit of a cgroup.
+///
+/// The cgroup can use up to `quota` microseconds of cpu time every `period`
+/// microseconds, a None `quota` means unlimited.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+pub struct CpuMax {
+    pub quota: Option<u64>,
+    pub period: u64,
+}
+
+/// Struct containing the memory accounting of a cgroup.
+///
+/// All values are in bytes, a None `max` means unlimited.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CgroupMemory {
+    pub current: u64,
+    pub max: Option<u64>,
+    pub stat: CgroupMemoryStat,
+}
+
+/// Struct containing the main values of the memory.stat of a cgroup.
+///
+/// All values are in bytes except `pgfault` and `pgmajfault`
+/// which are a number of events.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CgroupMemoryStat {
+    pub anon: u64,
+    pub file: u64,
+    pub kernel_stack: u64,
+    pub slab: u64,
+    pub sock: u64,
+    pub shmem: u64,
+    pub file_mapped: u64,
+    pub file_dirty: u64,
+    pub file_writeback: u64,
+    pub inactive_anon: u64,
+    pub active_anon: u64,
+    pub inactive_file: u64,
+    pub active_file: u64,
+    pub unevictable: u64,
+    pub pgfault: u64,
+    pub pgmajfault: u64,
+}
+
+/// Struct containing the I/O accounting of a cgroup for a block device (io.stat).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CgroupIo {
+    pub major: u32,
+    pub minor: u32,
+    pub rbytes: u64,
+    pub wbytes: u64,
+    pub rios: u64,
+    pub wios: u64,
+    pub dbytes: u64,
+    pub dios: u64,
+}
+
+/// Struct containing the resource accounting of a cgroup.
+///
+/// `memory` and `pids` are None when the controller is not enabled for the cgroup,
+/// same for `io` which is empty in that case.
+///
+/// With cgroup v1, each resource is read from the hierarchy of its controller
+/// (cpu/cpuacct, memory, blkio and pids), the v2 hierarchy being used for the
+/// ones not mounted as v1.
+#[derive(Debug, Clone, Serialize)]
+pub struct Cgroup {
+    /// Path of the cgroup relative to the cgroup filesystem (eg: /system.slice/ssh.service)
+    pub path: String,
+    pub mode: CgroupMode,
+    pub cpu: CgroupCpu,
+    pub memory: Option<CgroupMemory>,
+    pub io: Vec<CgroupIo>,
+    pub pids: Option<u64>,
+}
@@ -0,0 +1,137 @@
+use crate::cgroup::CgroupMode;
+use crate::disks::parse_mountinfo;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+/// Controllers of the cgroup v1 hierarchies.
+const CONTROLLERS: [&str; 13] = [
+    "cpu",
+    "cpuacct",
+    "cpuset",
+    "memory",
+    "devices",
+    "freezer",
+    "net_cls",
+    "blkio",
+    "perf_event",
+    "net_prio",
+    "hugetlb",
+    "pids",
+    "rdma",
+];
+
+/// A mounted cgroup hierarchy.
+#[derive(Debug, Clone)]
+pub(crate) struct CgroupMount {
+    /// Root of the mount within the hierarchy (not / inside a cgroup namespace or a bind mount)
+    pub(crate) root: String,
+    pub(crate) mount_point: String,
+    /// Super options of the mount (eg: rw,cpu,cpuacct), the controllers for a v1 hierarchy
+    pub(crate) options: Vec<String>,
+}
+
+impl CgroupMount {
+    /// Return the directory of the cgroup `path` in this hierarchy.
+    pub(crate) fn dir(&self, root: &SysRoot, path: &str) -> PathBuf {
+        // Cgroup paths are relative to the root of the hierarchy, not of the mount
+        let path = match self.root.as_str() {
+            "/" => path,
+            // Only strip whole components: /docker/abc isn't the root of /docker/abcdef
+            mount_root => match path.strip_prefix(mount_root) {
+                Some(stripped) if stripped.is_empty() || stripped.starts_with('/') => stripped,
+                _ => path,
+            },
+        };
+        // Resolve the mount point in the SysRoot when possible
+        let mount_point = match self.mount_point.strip_prefix("/sys/") {
+            Some(sys) => root.sys_path(sys),
+            None => PathBuf::from(&self.mount_point),
+        };
+
+        mount_point.join(path.trim_start_matches('/'))
+    }
+}
+
+/// The cgroup hierarchies mounted on the system.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CgroupMounts {
+    pub(crate) v1: Vec<CgroupMount>,
+    pub(crate) v2: Option<CgroupMount>,
+}
+
+impl CgroupMounts {
+    /// Return the v1 hierarchy of the `controller` if it's mounted.
+    pub(crate) fn v1(&self, controller: &str) -> Option<&CgroupMount> {
+        self.v1
+            .iter()
+            .find(|mount| mount.options.iter().any(|option| option == controller))
+    }
+
+    /// Return the [CgroupMode] of the system based on the mounted hierarchies.
+    pub(crate) fn mode(&self) -> Result<CgroupMode, Error> {
+        let has_v1 = CONTROLLERS
+            .iter()
+            .any(|controller| self.v1(controller).is_some());
+        match (has_v1, self.v2.is_some()) {
+            (true, true) => Ok(CgroupMode::Hybrid),
+            (true, false) => Ok(CgroupMode::V1),
+            (false, true) => Ok(CgroupMode::V2),
+            (false, false) => Err(Error::Unsupported("cgroup")),
+        }
+    }
+}
+
+/// Parse the cgroup hierarchies from the content of /proc/self/mountinfo.
+pub(crate) fn parse_cgroup_mounts<R: BufRead>(reader: R) -> Result<CgroupMounts, Error> {
+    let mut mounts = CgroupMounts::default();
+    for mount in parse_mountinfo(reader)? {
+        let is_v2 = match mount.fs_type.as_str() {
+            "cgroup" => false,
+            "cgroup2" => true,
+            _ => continue,
+        };
+        let mount = CgroupMount {
+            root: mount.root,
+            mount_point: mount.mount_point,
+            options: mount.super_options,
+        };
+
+        match is_v2 {
+            // Only keep the first one, the others being bind mounts
+            true if mounts.v2.is_none() => mounts.v2 = Some(mount),
+            false => mounts.v1.push(mount),
+            _ => {}
+        }
+    }
+
+    Ok(mounts)
+}
+
+/// Read the cgroup hierarchies mounted from /proc/self/mountinfo.
+pub(crate) fn read_cgroup_mounts(root: &SysRoot) -> Result<CgroupMounts, Error> {
+    let file = File::open(root.proc_path("self/mountinfo"))?;
+    parse_cgroup_mounts(BufReader::with_capacity(4096, file))
+}
+
+/// Get the [CgroupMode] of the system (cgroup v1, v2 or hybrid).
+///
+/// Return [Error::Unsupported] if no cgroup hierarchy is mounted.
+///
+/// [CgroupMode]: ../cgroup/enum.CgroupMode.html
+/// [Error::Unsupported]: ../enum.Error.html#variant.Unsupported
+pub fn get_cgroup_mode() -> Result<CgroupMode, Error> {
+    get_cgroup_mode_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the [CgroupMode] of the system reading from the given [SysRoot].
+///
+/// [CgroupMode]: ../cgroup/enum.CgroupMode.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cgroup_mode_with_root(root: &SysRoot) -> Result<CgroupMode, Error> {
+    read_cgroup_mounts(root)?.mode()
+}
@@ -0,0 +1,72 @@
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+/// A line of /proc/[pid]/cgroup.
+#[derive(Debug, Clone)]
+pub(crate) struct CgroupPath {
+    /// Controllers of the hierarchy, empty for the cgroup v2 one
+    pub(crate) controllers: Vec<String>,
+    pub(crate) path: String,
+}
+
+/// Parse each line of /proc/[pid]/cgroup (`hierarchy-ID:controller-list:cgroup-path`).
+pub(crate) fn parse_cgroup_paths<R: BufRead>(mut reader: R) -> Result<Vec<CgroupPath>, Error> {
+    let mut v_paths: Vec<CgroupPath> = Vec::new();
+    let mut lineno = 0;
+    let mut line = String::with_capacity(128);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let mut fields = line.trim_end().splitn(3, ':');
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(_), Some(controllers), Some(path)) => v_paths.push(CgroupPath {
+                controllers: controllers
+                    .split(',')
+                    .filter(|controller| !controller.is_empty())
+                    .map(|controller| controller.to_owned())
+                    .collect(),
+                path: path.to_owned(),
+            }),
+            _ => return Err(Error::parse("/proc/self/cgroup", lineno)),
+        }
+        line.clear();
+    }
+
+    Ok(v_paths)
+}
+
+/// Read the cgroup of each hierarchy of the current process from /proc/self/cgroup.
+pub(crate) fn read_cgroup_paths(root: &SysRoot) -> Result<Vec<CgroupPath>, Error> {
+    let file = File::open(root.proc_path("self/cgroup"))?;
+    parse_cgroup_paths(BufReader::with_capacity(512, file))
+}
+
+/// Parse the path of the cgroup v2 from the content of /proc/[pid]/cgroup.
+///
+/// Return [Error::Unsupported] if the process is not in a cgroup v2 hierarchy.
+///
+/// [Error::Unsupported]: ../enum.Error.html#variant.Unsupported
+pub fn parse_cgroup_path<R: BufRead>(reader: R) -> Result<String, Error> {
+    // The cgroup v2 line is always `0::/path`
+    parse_cgroup_paths(reader)?
+        .into_iter()
+        .find(|cgroup| cgroup.controllers.is_empty() && cgroup.path.starts_with('/'))
+        .map(|cgroup| cgroup.path)
+        .ok_or(Error::Unsupported("cgroup v2"))
+}
+
+/// Get the path of the cgroup v2 of the current process (eg: /system.slice/ssh.service).
+pub fn get_cgroup_path() -> Result<String, Error> {
+    get_cgroup_path_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the path of the cgroup v2 of the current process reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cgroup_path_with_root(root: &SysRoot) -> Result<String, Error> {
+    let file = File::open(root.proc_path("self/cgroup"))?;
+    parse_cgroup_path(BufReader::with_capacity(512, file))
+}
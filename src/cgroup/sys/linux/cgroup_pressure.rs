@@ -0,0 +1,54 @@
+use super::cgroup_mounts::read_cgroup_mounts;
+use super::cgroup_path::get_cgroup_path_with_root;
+use crate::pressure::{read_pressure, Pressure, PressureResource};
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+/// Get the [Pressure] of the `resource` for the cgroup at `path` (eg: /system.slice/ssh.service).
+///
+/// The pressure files are only available in the cgroup v2 hierarchy, return
+/// [Error::Unsupported] if it's not mounted or if the kernel doesn't support PSI.
+///
+/// [Pressure]: ../pressure/struct.Pressure.html
+/// [Error::Unsupported]: ../enum.Error.html#variant.Unsupported
+pub fn get_cgroup_pressure(path: &str, resource: PressureResource) -> Result<Pressure, Error> {
+    get_cgroup_pressure_with_root(&DEFAULT_ROOT, path, resource)
+}
+
+/// Get the [Pressure] of the `resource` for the cgroup at `path` reading from the given [SysRoot].
+///
+/// [Pressure]: ../pressure/struct.Pressure.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cgroup_pressure_with_root(
+    root: &SysRoot,
+    path: &str,
+    resource: PressureResource,
+) -> Result<Pressure, Error> {
+    let mounts = read_cgroup_mounts(root)?;
+    let mount = mounts.v2.ok_or(Error::Unsupported("cgroup v2"))?;
+
+    read_pressure(
+        &mount
+            .dir(root, path)
+            .join(format!("{}.pressure", resource.name())),
+    )
+}
+
+/// Get the [Pressure] of the `resource` for the cgroup of the current process.
+///
+/// [Pressure]: ../pressure/struct.Pressure.html
+pub fn get_current_cgroup_pressure(resource: PressureResource) -> Result<Pressure, Error> {
+    get_current_cgroup_pressure_with_root(&DEFAULT_ROOT, resource)
+}
+
+/// Get the [Pressure] of the `resource` for the cgroup of the current process
+/// reading from the given [SysRoot].
+///
+/// [Pressure]: ../pressure/struct.Pressure.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_current_cgroup_pressure_with_root(
+    root: &SysRoot,
+    resource: PressureResource,
+) -> Result<Pressure, Error> {
+    let path = get_cgroup_path_with_root(root)?;
+    get_cgroup_pressure_with_root(root, &path, resource)
+}
@@ -0,0 +1,250 @@
+use super::cgroups::{parse_value, read_optional};
+use crate::cgroup::{CgroupCpu, CgroupIo, CgroupMemory, CgroupMemoryStat, CpuMax};
+use crate::{Error, CLOCK_TICKS};
+
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+// The kernel reports "no limit" as PAGE_COUNTER_MAX in bytes, which is rounded
+// down to the page size (so it depends on it, up to 64KiB pages).
+const UNLIMITED: u64 = i64::MAX as u64 - 0xFFFF;
+
+/// Parse a flat keyed file (`key value` on each line) calling `f` for each entry.
+fn parse_keyed<R, F>(mut reader: R, file: &str, mut f: F) -> Result<(), Error>
+where
+    R: BufRead,
+    F: FnMut(&str, u64),
+{
+    let mut lineno = 0;
+    let mut line = String::with_capacity(64);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let mut fields = line.split_whitespace();
+        if let (Some(key), Some(value)) = (fields.next(), fields.next()) {
+            f(
+                key,
+                value
+                    .parse::<u64>()
+                    .map_err(|_| Error::parse(file, lineno))?,
+            );
+        }
+        line.clear();
+    }
+
+    Ok(())
+}
+
+/// Parse the [CgroupMemoryStat] from the content of the cgroup v1 memory.stat.
+///
+/// Values not exposed by cgroup v1 (kernel_stack, slab and sock) are zeroed.
+///
+/// [CgroupMemoryStat]: ../cgroup/struct.CgroupMemoryStat.html
+pub fn parse_cgroup_v1_memory_stat<R: BufRead>(reader: R) -> Result<CgroupMemoryStat, Error> {
+    let mut stat = CgroupMemoryStat::default();
+    parse_keyed(reader, "memory.stat", |key, value| {
+        // Ignore the hierarchical (total_*) values
+        match key {
+            "rss" => stat.anon = value,
+            "cache" => stat.file = value,
+            "shmem" => stat.shmem = value,
+            "mapped_file" => stat.file_mapped = value,
+            "dirty" => stat.file_dirty = value,
+            "writeback" => stat.file_writeback = value,
+            "inactive_anon" => stat.inactive_anon = value,
+            "active_anon" => stat.active_anon = value,
+            "inactive_file" => stat.inactive_file = value,
+            "active_file" => stat.active_file = value,
+            "unevictable" => stat.unevictable = value,
+            "pgfault" => stat.pgfault = value,
+            "pgmajfault" => stat.pgmajfault = value,
+            _ => {}
+        }
+    })?;
+
+    Ok(stat)
+}
+
+/// Parse the [CgroupIo] of each block device from the content of the cgroup v1
+/// blkio.throttle.io_service_bytes (`bytes`) and blkio.throttle.io_serviced (`ios`).
+///
+/// [CgroupIo]: ../cgroup/struct.CgroupIo.html
+pub fn parse_cgroup_v1_blkio<R: BufRead>(bytes: R, ios: R) -> Result<Vec<CgroupIo>, Error> {
+    let mut v_io: Vec<CgroupIo> = Vec::new();
+
+    for (mut reader, file, is_bytes) in [
+        (bytes, "blkio.throttle.io_service_bytes", true),
+        (ios, "blkio.throttle.io_serviced", false),
+    ] {
+        let mut lineno = 0;
+        let mut line = String::with_capacity(64);
+        while reader.read_line(&mut line)? != 0 {
+            lineno += 1;
+            let err = || Error::parse(file, lineno);
+            // 8:0 Read 4096 (and a last `Total 4096` line)
+            let mut fields = line.split_whitespace();
+            let (device, op, value) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(device), Some(op), Some(value)) => (device, op, value),
+                _ => {
+                    line.clear();
+                    continue;
+                }
+            };
+
+            let mut parts = device.splitn(2, ':');
+            let major = parts
+                .next()
+                .and_then(|major| major.parse::<u32>().ok())
+                .ok_or_else(err)?;
+            let minor = parts
+                .next()
+                .and_then(|minor| minor.parse::<u32>().ok())
+                .ok_or_else(err)?;
+            let value = value.parse::<u64>().map_err(|_| err())?;
+
+            let idx = match v_io
+                .iter()
+                .position(|io| io.major == major && io.minor == minor)
+            {
+                Some(idx) => idx,
+                None => {
+                    v_io.push(CgroupIo {
+                        major,
+                        minor,
+                        ..Default::default()
+                    });
+                    v_io.len() - 1
+                }
+            };
+            let io = &mut v_io[idx];
+            match (op, is_bytes) {
+                ("Read", true) => io.rbytes = value,
+                ("Write", true) => io.wbytes = value,
+                ("Discard", true) => io.dbytes = value,
+                ("Read", false) => io.rios = value,
+                ("Write", false) => io.wios = value,
+                ("Discard", false) => io.dios = value,
+                _ => {}
+            }
+            line.clear();
+        }
+    }
+
+    Ok(v_io)
+}
+
+/// Read the [CgroupCpu] from the cgroup v1 cpuacct directory and the optional cpu one.
+///
+/// [CgroupCpu]: ../cgroup/struct.CgroupCpu.html
+pub(crate) fn read_v1_cpu(cpuacct: &Path, cpu: Option<&Path>) -> Result<CgroupCpu, Error> {
+    let mut cgroup_cpu = CgroupCpu {
+        // Nanoseconds
+        usage_usec: parse_value(
+            &fs::read_to_string(cpuacct.join("cpuacct.usage"))?,
+            "cpuacct.usage",
+        )? / 1000,
+        ..Default::default()
+    };
+
+    // USER_HZ
+    let file = File::open(cpuacct.join("cpuacct.stat"))?;
+    parse_keyed(
+        BufReader::with_capacity(64, file),
+        "cpuacct.stat",
+        |key, value| match key {
+            "user" => cgroup_cpu.user_usec = value * 1_000_000 / *CLOCK_TICKS,
+            "system" => cgroup_cpu.system_usec = value * 1_000_000 / *CLOCK_TICKS,
+            _ => {}
+        },
+    )?;
+
+    let cpu = match cpu {
+        Some(cpu) => cpu,
+        None => return Ok(cgroup_cpu),
+    };
+
+    if let Some(content) = read_optional(&cpu.join("cpu.stat"))? {
+        parse_keyed(content.as_bytes(), "cpu.stat", |key, value| match key {
+            "nr_periods" => cgroup_cpu.nr_periods = Some(value),
+            "nr_throttled" => cgroup_cpu.nr_throttled = Some(value),
+            // Nanoseconds
+            "throttled_time" => cgroup_cpu.throttled_usec = Some(value / 1000),
+            _ => {}
+        })?;
+    }
+
+    cgroup_cpu.max = read_v1_cpu_max(cpu)?;
+
+    Ok(cgroup_cpu)
+}
+
+/// Read the [CpuMax] from the cgroup v1 cpu directory `dir`.
+///
+/// [CpuMax]: ../cgroup/struct.CpuMax.html
+pub(crate) fn read_v1_cpu_max(dir: &Path) -> Result<Option<CpuMax>, Error> {
+    // Both are missing without CONFIG_CFS_BANDWIDTH
+    match (
+        read_optional(&dir.join("cpu.cfs_quota_us"))?,
+        read_optional(&dir.join("cpu.cfs_period_us"))?,
+    ) {
+        (Some(quota), Some(period)) => Ok(Some(CpuMax {
+            // -1 means unlimited
+            quota: quota.trim().parse::<u64>().ok(),
+            period: parse_value(&period, "cpu.cfs_period_us")?,
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// Read the [CgroupMemory] from the cgroup v1 memory directory `dir`.
+///
+/// [CgroupMemory]: ../cgroup/struct.CgroupMemory.html
+pub(crate) fn read_v1_memory(dir: &Path) -> Result<CgroupMemory, Error> {
+    let current = parse_value(
+        &fs::read_to_string(dir.join("memory.usage_in_bytes"))?,
+        "memory.usage_in_bytes",
+    )?;
+    let max = read_v1_memory_max(dir)?;
+    let file = File::open(dir.join("memory.stat"))?;
+
+    Ok(CgroupMemory {
+        current,
+        max,
+        stat: parse_cgroup_v1_memory_stat(BufReader::with_capacity(2048, file))?,
+    })
+}
+
+/// Read the memory limit (in bytes) from the cgroup v1 memory directory `dir`, None if unlimited.
+pub(crate) fn read_v1_memory_max(dir: &Path) -> Result<Option<u64>, Error> {
+    let max = match read_optional(&dir.join("memory.limit_in_bytes"))? {
+        Some(max) => parse_value(&max, "memory.limit_in_bytes")?,
+        None => return Ok(None),
+    };
+
+    Ok(if max >= UNLIMITED { None } else { Some(max) })
+}
+
+/// Read the [CgroupIo] from the cgroup v1 blkio directory `dir`.
+///
+/// [CgroupIo]: ../cgroup/struct.CgroupIo.html
+pub(crate) fn read_v1_io(dir: &Path) -> Result<Vec<CgroupIo>, Error> {
+    // Both are missing without CONFIG_BLK_DEV_THROTTLING
+    match (
+        read_optional(&dir.join("blkio.throttle.io_service_bytes"))?,
+        read_optional(&dir.join("blkio.throttle.io_serviced"))?,
+    ) {
+        (Some(bytes), Some(ios)) => parse_cgroup_v1_blkio(bytes.as_bytes(), ios.as_bytes()),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Read the number of processes from the cgroup v1 pids directory `dir`.
+pub(crate) fn read_v1_pids(dir: &Path) -> Result<Option<u64>, Error> {
+    // The root cgroup doesn't have any pids.current
+    match read_optional(&dir.join("pids.current"))? {
+        Some(content) => Ok(Some(parse_value(&content, "pids.current")?)),
+        None => Ok(None),
+    }
+}
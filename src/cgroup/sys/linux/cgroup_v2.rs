@@ -0,0 +1,231 @@
+use super::cgroups::{parse_max, parse_value, read_optional};
+use crate::cgroup::{CgroupCpu, CgroupIo, CgroupMemory, CgroupMemoryStat, CpuMax};
+use crate::Error;
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Parse the [CgroupCpu] (without the cpu.max limit) from the content of cpu.stat.
+///
+/// [CgroupCpu]: ../cgroup/struct.CgroupCpu.html
+pub fn parse_cgroup_cpu_stat<R: BufRead>(mut reader: R) -> Result<CgroupCpu, Error> {
+    let mut cpu = CgroupCpu::default();
+    let mut has_usage = false;
+    let mut lineno = 0;
+    let mut line = String::with_capacity(64);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let mut fields = line.split_whitespace();
+        let (key, value) = match (fields.next(), fields.next()) {
+            (Some(key), Some(value)) => (
+                key,
+                value
+                    .parse::<u64>()
+                    .map_err(|_| Error::parse("cpu.stat", lineno))?,
+            ),
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+
+        match key {
+            "usage_usec" => {
+                cpu.usage_usec = value;
+                has_usage = true;
+            }
+            "user_usec" => cpu.user_usec = value,
+            "system_usec" => cpu.system_usec = value,
+            "nr_periods" => cpu.nr_periods = Some(value),
+            "nr_throttled" => cpu.nr_throttled = Some(value),
+            "throttled_usec" => cpu.throttled_usec = Some(value),
+            _ => {}
+        }
+        line.clear();
+    }
+
+    if !has_usage {
+        return Err(Error::MissingField("usage_usec"));
+    }
+
+    Ok(cpu)
+}
+
+/// Parse the [CpuMax] from the content of cpu.max (`$MAX $PERIOD`).
+///
+/// [CpuMax]: ../cgroup/struct.CpuMax.html
+pub fn parse_cgroup_cpu_max(content: &str) -> Result<CpuMax, Error> {
+    let mut fields = content.split_whitespace();
+    let quota = parse_max(nth!(fields, 0, "max")?, "cpu.max")?;
+    let period = parse_value(nth!(fields, 0, "period")?, "cpu.max")?;
+
+    Ok(CpuMax { quota, period })
+}
+
+/// Parse the [CgroupMemoryStat] from the content of memory.stat.
+///
+/// [CgroupMemoryStat]: ../cgroup/struct.CgroupMemoryStat.html
+pub fn parse_cgroup_memory_stat<R: BufRead>(mut reader: R) -> Result<CgroupMemoryStat, Error> {
+    let mut stat = CgroupMemoryStat::default();
+    let mut lineno = 0;
+    let mut line = String::with_capacity(64);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let mut fields = line.split_whitespace();
+        let field = match fields.next() {
+            Some("anon") => &mut stat.anon,
+            Some("file") => &mut stat.file,
+            Some("kernel_stack") => &mut stat.kernel_stack,
+            Some("slab") => &mut stat.slab,
+            Some("sock") => &mut stat.sock,
+            Some("shmem") => &mut stat.shmem,
+            Some("file_mapped") => &mut stat.file_mapped,
+            Some("file_dirty") => &mut stat.file_dirty,
+            Some("file_writeback") => &mut stat.file_writeback,
+            Some("inactive_anon") => &mut stat.inactive_anon,
+            Some("active_anon") => &mut stat.active_anon,
+            Some("inactive_file") => &mut stat.inactive_file,
+            Some("active_file") => &mut stat.active_file,
+            Some("unevictable") => &mut stat.unevictable,
+            Some("pgfault") => &mut stat.pgfault,
+            Some("pgmajfault") => &mut stat.pgmajfault,
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+
+        *field = fields
+            .next()
+            .and_then(|value| value.parse::<u64>().ok())
+            .ok_or_else(|| Error::parse("memory.stat", lineno))?;
+        line.clear();
+    }
+
+    Ok(stat)
+}
+
+/// Parse the [CgroupIo] of each block device from the content of io.stat.
+///
+/// [CgroupIo]: ../cgroup/struct.CgroupIo.html
+pub fn parse_cgroup_io_stat<R: BufRead>(mut reader: R) -> Result<Vec<CgroupIo>, Error> {
+    let mut v_io: Vec<CgroupIo> = Vec::new();
+    let mut lineno = 0;
+    let mut line = String::with_capacity(128);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let err = || Error::parse("io.stat", lineno);
+        // 8:16 rbytes=1459200 wbytes=314773504 rios=192 wios=353 dbytes=0 dios=0
+        let mut fields = line.split_whitespace();
+        let device = match fields.next() {
+            Some(device) => device,
+            None => {
+                line.clear();
+                continue;
+            }
+        };
+
+        let mut io = CgroupIo::default();
+        let mut parts = device.splitn(2, ':');
+        io.major = parts
+            .next()
+            .and_then(|major| major.parse::<u32>().ok())
+            .ok_or_else(err)?;
+        io.minor = parts
+            .next()
+            .and_then(|minor| minor.parse::<u32>().ok())
+            .ok_or_else(err)?;
+
+        for field in fields {
+            let mut parts = field.splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key, value),
+                _ => return Err(err()),
+            };
+            let counter = match key {
+                "rbytes" => &mut io.rbytes,
+                "wbytes" => &mut io.wbytes,
+                "rios" => &mut io.rios,
+                "wios" => &mut io.wios,
+                "dbytes" => &mut io.dbytes,
+                "dios" => &mut io.dios,
+                _ => continue,
+            };
+            *counter = value.parse::<u64>().map_err(|_| err())?;
+        }
+
+        v_io.push(io);
+        line.clear();
+    }
+
+    Ok(v_io)
+}
+
+/// Read the [CgroupCpu] from the cgroup v2 directory `dir`.
+///
+/// [CgroupCpu]: ../cgroup/struct.CgroupCpu.html
+pub(crate) fn read_v2_cpu(dir: &Path) -> Result<CgroupCpu, Error> {
+    let file = File::open(dir.join("cpu.stat"))?;
+    let mut cpu = parse_cgroup_cpu_stat(BufReader::with_capacity(512, file))?;
+    cpu.max = read_v2_cpu_max(dir)?;
+
+    Ok(cpu)
+}
+
+/// Read the [CpuMax] from the cgroup v2 directory `dir`, None if there's no cpu.max.
+///
+/// [CpuMax]: ../cgroup/struct.CpuMax.html
+pub(crate) fn read_v2_cpu_max(dir: &Path) -> Result<Option<CpuMax>, Error> {
+    match read_optional(&dir.join("cpu.max"))? {
+        Some(content) => Ok(Some(parse_cgroup_cpu_max(&content)?)),
+        None => Ok(None),
+    }
+}
+
+/// Read the [CgroupMemory] from the cgroup v2 directory `dir`.
+///
+/// [CgroupMemory]: ../cgroup/struct.CgroupMemory.html
+pub(crate) fn read_v2_memory(dir: &Path) -> Result<Option<CgroupMemory>, Error> {
+    let current = match read_optional(&dir.join("memory.current"))? {
+        Some(current) => parse_value(&current, "memory.current")?,
+        None => return Ok(None),
+    };
+    let max = read_v2_memory_max(dir)?;
+    let file = File::open(dir.join("memory.stat"))?;
+
+    Ok(Some(CgroupMemory {
+        current,
+        max,
+        stat: parse_cgroup_memory_stat(BufReader::with_capacity(2048, file))?,
+    }))
+}
+
+/// Read the memory limit (in bytes) from the cgroup v2 directory `dir`, None if unlimited.
+pub(crate) fn read_v2_memory_max(dir: &Path) -> Result<Option<u64>, Error> {
+    // The root cgroup doesn't have any memory.max
+    match read_optional(&dir.join("memory.max"))? {
+        Some(max) => parse_max(&max, "memory.max"),
+        None => Ok(None),
+    }
+}
+
+/// Read the [CgroupIo] from the cgroup v2 directory `dir`.
+///
+/// [CgroupIo]: ../cgroup/struct.CgroupIo.html
+pub(crate) fn read_v2_io(dir: &Path) -> Result<Vec<CgroupIo>, Error> {
+    match read_optional(&dir.join("io.stat"))? {
+        Some(content) => parse_cgroup_io_stat(content.as_bytes()),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Read the number of processes from the cgroup v2 directory `dir`.
+pub(crate) fn read_v2_pids(dir: &Path) -> Result<Option<u64>, Error> {
+    match read_optional(&dir.join("pids.current"))? {
+        Some(content) => Ok(Some(parse_value(&content, "pids.current")?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,276 @@
+use super::cgroup_mounts::{read_cgroup_mounts, CgroupMounts};
+use super::cgroup_path::{read_cgroup_paths, CgroupPath};
+use super::cgroup_v1::{
+    read_v1_cpu, read_v1_cpu_max, read_v1_io, read_v1_memory, read_v1_memory_max, read_v1_pids,
+};
+use super::cgroup_v2::{
+    read_v2_cpu, read_v2_cpu_max, read_v2_io, read_v2_memory, read_v2_memory_max, read_v2_pids,
+};
+use crate::cgroup::{Cgroup, CgroupMemory, CpuMax};
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// Read the content of `path` or return None if it doesn't exist
+/// (the controller is not enabled for the cgroup).
+pub(crate) fn read_optional(path: &Path) -> Result<Option<String>, Error> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(Error::from(err)),
+    }
+}
+
+/// Parse a single value file, `max` meaning unlimited (None).
+pub(crate) fn parse_max(content: &str, file: &str) -> Result<Option<u64>, Error> {
+    match content.trim() {
+        "max" => Ok(None),
+        value => value
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|_| Error::parse(file, 1)),
+    }
+}
+
+/// Parse a single value file.
+pub(crate) fn parse_value(content: &str, file: &str) -> Result<u64, Error> {
+    content
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| Error::parse(file, 1))
+}
+
+/// Return the directories of the cgroup of the current process and of its ancestors
+/// (up to the root of the mount) in the v1 hierarchy of `controller` if it's mounted,
+/// or in the v2 one otherwise. The bool is true for a v1 hierarchy.
+fn current_hierarchy(
+    root: &SysRoot,
+    mounts: &CgroupMounts,
+    paths: &[CgroupPath],
+    controller: &str,
+) -> Option<(bool, Vec<PathBuf>)> {
+    let (is_v1, mount, path) = match mounts.v1(controller) {
+        Some(mount) => (
+            true,
+            mount,
+            paths
+                .iter()
+                .find(|cgroup| cgroup.controllers.iter().any(|c| c == controller))?,
+        ),
+        None => (
+            false,
+            mounts.v2.as_ref()?,
+            paths.iter().find(|cgroup| cgroup.controllers.is_empty())?,
+        ),
+    };
+
+    let top = mount.dir(root, &mount.root);
+    let dirs = mount
+        .dir(root, &path.path)
+        .ancestors()
+        .take_while(|dir| dir.starts_with(&top))
+        .map(|dir| dir.to_path_buf())
+        .collect();
+
+    Some((is_v1, dirs))
+}
+
+/// Get the number of cpus the cgroup of the current process is allowed to use
+/// (quota / period), None if it's not limited.
+///
+/// The limits of the ancestors of the cgroup are taken into account as they also apply.
+pub fn get_cgroup_cpu_limit() -> Result<Option<f64>, Error> {
+    get_cgroup_cpu_limit_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the number of cpus the cgroup of the current process is allowed to use
+/// reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cgroup_cpu_limit_with_root(root: &SysRoot) -> Result<Option<f64>, Error> {
+    let mounts = read_cgroup_mounts(root)?;
+    let paths = read_cgroup_paths(root)?;
+    let (is_v1, dirs) = match current_hierarchy(root, &mounts, &paths, "cpu") {
+        Some(hierarchy) => hierarchy,
+        None => return Ok(None),
+    };
+
+    let mut limit: Option<f64> = None;
+    for dir in dirs {
+        let max = match is_v1 {
+            true => read_v1_cpu_max(&dir)?,
+            false => read_v2_cpu_max(&dir)?,
+        };
+        if let Some(CpuMax {
+            quota: Some(quota),
+            period,
+        }) = max
+        {
+            if period > 0 {
+                let cpus = quota as f64 / period as f64;
+                limit = Some(limit.map_or(cpus, |limit| limit.min(cpus)));
+            }
+        }
+    }
+
+    Ok(limit)
+}
+
+/// Get the memory limit (in bytes) of the cgroup of the current process, None if it's not limited.
+///
+/// The limits of the ancestors of the cgroup are taken into account as they also apply.
+pub fn get_cgroup_memory_limit() -> Result<Option<u64>, Error> {
+    get_cgroup_memory_limit_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the memory limit (in bytes) of the cgroup of the current process
+/// reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cgroup_memory_limit_with_root(root: &SysRoot) -> Result<Option<u64>, Error> {
+    let mounts = read_cgroup_mounts(root)?;
+    let paths = read_cgroup_paths(root)?;
+    let (is_v1, dirs) = match current_hierarchy(root, &mounts, &paths, "memory") {
+        Some(hierarchy) => hierarchy,
+        None => return Ok(None),
+    };
+
+    let mut limit: Option<u64> = None;
+    for dir in dirs {
+        let max = match is_v1 {
+            true => read_v1_memory_max(&dir)?,
+            false => read_v2_memory_max(&dir)?,
+        };
+        if let Some(max) = max {
+            limit = Some(limit.map_or(max, |limit| limit.min(max)));
+        }
+    }
+
+    Ok(limit)
+}
+
+/// Read the [CgroupMemory] of the cgroup of the current process,
+/// None if the memory controller is not enabled.
+///
+/// [CgroupMemory]: ../cgroup/struct.CgroupMemory.html
+pub(crate) fn read_current_memory(root: &SysRoot) -> Result<Option<CgroupMemory>, Error> {
+    let mounts = read_cgroup_mounts(root)?;
+    let paths = read_cgroup_paths(root)?;
+    let dir = match current_hierarchy(root, &mounts, &paths, "memory") {
+        Some((true, dirs)) => return Ok(Some(read_v1_memory(&dirs[0])?)),
+        Some((false, dirs)) => dirs[0].clone(),
+        None => return Ok(None),
+    };
+
+    read_v2_memory(&dir)
+}
+
+/// Read the [Cgroup] accounting, `path` being the cgroup for the v2 hierarchy
+/// and `v1_path` returning the cgroup for each v1 controller.
+///
+/// [Cgroup]: ../cgroup/struct.Cgroup.html
+fn read_cgroup<F>(
+    root: &SysRoot,
+    mounts: &CgroupMounts,
+    path: &str,
+    v1_path: F,
+) -> Result<Cgroup, Error>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mode = mounts.mode()?;
+    let v2_dir = mounts.v2.as_ref().map(|mount| mount.dir(root, path));
+    let v1_dir = |controller: &str| -> Option<PathBuf> {
+        let mount = mounts.v1(controller)?;
+        Some(mount.dir(root, &v1_path(controller)?))
+    };
+
+    // Prefer the v1 controllers as they're not available in the v2
+    // hierarchy when running in hybrid mode.
+    let cpu = match (v1_dir("cpuacct"), &v2_dir) {
+        (Some(cpuacct), _) => read_v1_cpu(&cpuacct, v1_dir("cpu").as_deref())?,
+        (None, Some(dir)) => read_v2_cpu(dir)?,
+        (None, None) => return Err(Error::Unsupported("cgroup cpu accounting")),
+    };
+    let memory = match (v1_dir("memory"), &v2_dir) {
+        (Some(dir), _) => Some(read_v1_memory(&dir)?),
+        (None, Some(dir)) => read_v2_memory(dir)?,
+        (None, None) => None,
+    };
+    let io = match (v1_dir("blkio"), &v2_dir) {
+        (Some(dir), _) => read_v1_io(&dir)?,
+        (None, Some(dir)) => read_v2_io(dir)?,
+        (None, None) => Vec::new(),
+    };
+    let pids = match (v1_dir("pids"), &v2_dir) {
+        (Some(dir), _) => read_v1_pids(&dir)?,
+        (None, Some(dir)) => read_v2_pids(dir)?,
+        (None, None) => None,
+    };
+
+    Ok(Cgroup {
+        path: path.to_owned(),
+        mode,
+        cpu,
+        memory,
+        io,
+        pids,
+    })
+}
+
+/// Get the [Cgroup] accounting of the cgroup at `path` (eg: /system.slice/ssh.service).
+///
+/// The cgroup v1, v2 or hybrid mode is detected using /proc/self/mountinfo, with cgroup v1
+/// the cgroup is read at `path` in the hierarchy of each controller.
+/// Return [Error::Unsupported] if no cgroup hierarchy is mounted.
+///
+/// [Cgroup]: ../cgroup/struct.Cgroup.html
+/// [Error::Unsupported]: ../enum.Error.html#variant.Unsupported
+pub fn get_cgroup(path: &str) -> Result<Cgroup, Error> {
+    get_cgroup_with_root(&DEFAULT_ROOT, path)
+}
+
+/// Get the [Cgroup] accounting of the cgroup at `path` reading from the given [SysRoot].
+///
+/// [Cgroup]: ../cgroup/struct.Cgroup.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cgroup_with_root(root: &SysRoot, path: &str) -> Result<Cgroup, Error> {
+    let mounts = read_cgroup_mounts(root)?;
+    read_cgroup(root, &mounts, path, |_| Some(path.to_owned()))
+}
+
+/// Get the [Cgroup] accounting of the cgroup of the current process.
+///
+/// [Cgroup]: ../cgroup/struct.Cgroup.html
+pub fn get_current_cgroup() -> Result<Cgroup, Error> {
+    get_current_cgroup_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the [Cgroup] accounting of the cgroup of the current process reading from the given [SysRoot].
+///
+/// [Cgroup]: ../cgroup/struct.Cgroup.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_current_cgroup_with_root(root: &SysRoot) -> Result<Cgroup, Error> {
+    let mounts = read_cgroup_mounts(root)?;
+    let paths = read_cgroup_paths(root)?;
+
+    let v1_path = |controller: &str| -> Option<String> {
+        paths
+            .iter()
+            .find(|cgroup| cgroup.controllers.iter().any(|c| c == controller))
+            .map(|cgroup| cgroup.path.clone())
+    };
+    // The path of the v2 hierarchy, or of the cpu accounting one on cgroup v1
+    let path = paths
+        .iter()
+        .find(|cgroup| cgroup.controllers.is_empty())
+        .map(|cgroup| cgroup.path.clone())
+        .or_else(|| v1_path("cpuacct"))
+        .ok_or(Error::MissingField("cgroup"))?;
+
+    read_cgroup(root, &mounts, &path, v1_path)
+}
@@ -0,0 +1,13 @@
+mod cgroup_mounts;
+mod cgroup_path;
+mod cgroup_pressure;
+mod cgroup_v1;
+mod cgroup_v2;
+mod cgroups;
+
+pub use cgroup_mounts::*;
+pub use cgroup_path::*;
+pub use cgroup_pressure::*;
+pub use cgroup_v1::*;
+pub use cgroup_v2::*;
+pub use cgroups::*;
@@ -47,6 +47,85 @@ impl CpuTimes {
     }
 }
 
+/// Struct containing the percentage of time the cpu spent in each state
+/// between two [CpuTimes] snapshots.
+///
+/// All values are in percent (0.0 - 100.0).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CpuUsage {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+    pub guest: f64,
+    pub guest_nice: f64,
+}
+
+impl CpuUsage {
+    /// Compute the [CpuUsage] between two [CpuTimes] snapshots.
+    ///
+    /// Return None if no time elapsed between `prev` and `curr` or if the total
+    /// time went backward (counters reset, cpu hotplug, ...). A single counter
+    /// going backward (iowait can decrease, see proc(5)) counts as no time spent.
+    pub fn from_delta(prev: &CpuTimes, curr: &CpuTimes) -> Option<CpuUsage> {
+        curr.total_time().checked_sub(prev.total_time())?;
+
+        let delta = |prev: u64, curr: u64| curr.saturating_sub(prev);
+        let delta_times = CpuTimes {
+            user: delta(prev.user, curr.user),
+            nice: delta(prev.nice, curr.nice),
+            system: delta(prev.system, curr.system),
+            idle: delta(prev.idle, curr.idle),
+            iowait: delta(prev.iowait, curr.iowait),
+            irq: delta(prev.irq, curr.irq),
+            softirq: delta(prev.softirq, curr.softirq),
+            steal: delta(prev.steal, curr.steal),
+            guest: delta(prev.guest, curr.guest),
+            guest_nice: delta(prev.guest_nice, curr.guest_nice),
+        };
+        let total = delta_times.total_time();
+        if total == 0 {
+            return None;
+        }
+
+        let percent = |delta: u64| delta as f64 * 100.0 / total as f64;
+
+        Some(CpuUsage {
+            user: percent(delta_times.user),
+            nice: percent(delta_times.nice),
+            system: percent(delta_times.system),
+            idle: percent(delta_times.idle),
+            iowait: percent(delta_times.iowait),
+            irq: percent(delta_times.irq),
+            softirq: percent(delta_times.softirq),
+            steal: percent(delta_times.steal),
+            guest: percent(delta_times.guest),
+            guest_nice: percent(delta_times.guest_nice),
+        })
+    }
+
+    /// Return the percentage of time the cpu has been busy
+    pub fn busy(&self) -> f64 {
+        self.user + self.nice + self.system + self.irq + self.softirq + self.steal
+    }
+}
+
+/// Struct containing the [CpuUsage] of the system and of each cpu
+/// as returned by a [CpuUsageSampler].
+///
+/// `percpu` is indexed by the cpu id, entries are None when the cpu
+/// was offline in one of the snapshots or if its counters were reset.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct CpuUsageSample {
+    pub total: Option<CpuUsage>,
+    pub percpu: Vec<Option<CpuUsage>>,
+}
+
 #[cfg(target_os = "macos")]
 #[repr(C)]
 pub(crate) struct host_cpu_load_info {
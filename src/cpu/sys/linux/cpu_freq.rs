@@ -1,28 +1,37 @@
-use std::io::Error;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
 use std::{
     fs::File,
-    io::{prelude::*, BufReader, ErrorKind},
+    io::{prelude::*, BufReader},
 };
 
-/// Get the cpufreq as f64 (in MHz).
-pub fn get_cpufreq() -> Result<f64, Error> {
-    let file = File::open("/proc/cpuinfo")?;
-    let mut file = BufReader::with_capacity(1024, file);
-
+/// Parse the cpufreq as f64 (in MHz) from the content of /proc/cpuinfo.
+///
+/// The first `cpu MHz` entry found is returned.
+pub fn parse_cpufreq<R: BufRead>(mut reader: R) -> Result<f64, Error> {
     let mut line = String::with_capacity(256);
-    while file.read_line(&mut line)? != 0 {
-        let lenght = line.len();
-        if lenght > 7 && lenght < 48 && &line[..7] == "cpu MHz" {
-            match line[11..lenght - 1].parse::<f64>() {
-                Ok(val) => return Ok(val),
-                Err(_) => {
-                    line.clear();
-                    continue;
-                }
-            };
+    while reader.read_line(&mut line)? != 0 {
+        // Lines are in the form of `cpu MHz\t\t: 2400.000`
+        if line.starts_with("cpu MHz") {
+            if let Some(Ok(val)) = line.split(':').nth(1).map(|v| v.trim().parse::<f64>()) {
+                return Ok(val);
+            }
         }
         line.clear();
     }
 
-    Err(Error::new(ErrorKind::Other, "Couldn't get the cpufreq"))
+    Err(Error::MissingField("cpu MHz"))
+}
+
+/// Get the cpufreq as f64 (in MHz).
+pub fn get_cpufreq() -> Result<f64, Error> {
+    get_cpufreq_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the cpufreq as f64 (in MHz) reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cpufreq_with_root(root: &SysRoot) -> Result<f64, Error> {
+    let file = File::open(root.proc_path("cpuinfo"))?;
+    parse_cpufreq(BufReader::with_capacity(1024, file))
 }
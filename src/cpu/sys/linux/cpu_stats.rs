@@ -1,45 +1,42 @@
 use crate::cpu::CpuStats;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
 
 use std::{
     fs::File,
-    io::{prelude::*, BufReader, Error, ErrorKind},
+    io::{prelude::*, BufReader},
 };
 
-/// Get basic [CpuStats] info the host.
-///
-/// It only contains raw information, to get the delta you need
-/// to get the diff between N and N-1.
+/// Parse the [CpuStats] from the content of /proc/stat.
 ///
 /// [CpuStats]: ../cpu/struct.CpuStats.html
-pub fn get_cpustats() -> Result<CpuStats, Error> {
-    let file = File::open("/proc/stat")?;
-    let mut file = BufReader::with_capacity(1024, file);
-
-    let mut matched_lines = 0u8;
+pub fn parse_cpustats<R: BufRead>(mut reader: R) -> Result<CpuStats, Error> {
+    // Keep track of the fields we've found (in the order of CpuStats)
+    let mut matched = [false; 6];
     let mut cpuctx = CpuStats::default();
+    let mut lineno = 0;
     let mut line = String::with_capacity(128);
-    while file.read_line(&mut line)? != 0 {
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
         // We only need 6 values which can be detected by their 4first bytes
-        let first_bytes = &line.as_bytes()[..4];
-        match first_bytes {
-            b"intr" | b"ctxt" | b"soft" | b"proc" => {}
+        match line.as_bytes().get(..4) {
+            Some(b"intr" | b"ctxt" | b"soft" | b"proc") => {}
             _ => {
                 line.clear();
                 continue;
             }
         }
 
-        // Split the line at the ' ' separator
-        let mut parts = line.splitn(3, ' ');
+        // Split the line at the whitespaces
+        let mut parts = line.split_whitespace();
         // Check if if the value we search is the splitted one
         // if so, return a pointer to the memory zone we'll modify.
-        let field = match parts.next() {
-            Some("intr") => &mut cpuctx.interrupts,
-            Some("ctxt") => &mut cpuctx.ctx_switches,
-            Some("softirq") => &mut cpuctx.soft_interrupts,
-            Some("processes") => &mut cpuctx.processes,
-            Some("procs_running") => &mut cpuctx.procs_running,
-            Some("procs_blocked") => &mut cpuctx.procs_blocked,
+        let (idx, field) = match parts.next() {
+            Some("intr") => (0, &mut cpuctx.interrupts),
+            Some("ctxt") => (1, &mut cpuctx.ctx_switches),
+            Some("softirq") => (2, &mut cpuctx.soft_interrupts),
+            Some("processes") => (3, &mut cpuctx.processes),
+            Some("procs_running") => (4, &mut cpuctx.procs_running),
+            Some("procs_blocked") => (5, &mut cpuctx.procs_blocked),
             _ => {
                 line.clear();
                 continue;
@@ -50,13 +47,10 @@ pub fn get_cpustats() -> Result<CpuStats, Error> {
         match parts.next() {
             Some(value) => {
                 // Increment the field we previously got (pointer)
-                *field = {
-                    // Trim to only get the bytes value
-                    let rval = value.trim().parse::<u64>().unwrap();
-
-                    matched_lines += 1;
-                    rval
-                }
+                *field = value
+                    .parse::<u64>()
+                    .map_err(|_| Error::parse("/proc/stat", lineno))?;
+                matched[idx] = true;
             }
 
             None => {
@@ -66,15 +60,40 @@ pub fn get_cpustats() -> Result<CpuStats, Error> {
         }
 
         // If we've found all our information, we can return.
-        if matched_lines == 6 {
+        if matched.iter().all(|m| *m) {
             return Ok(cpuctx);
         }
 
         line.clear();
     }
 
-    Err(Error::new(
-        ErrorKind::Other,
-        "Couldn't find all the informations for CpuStats",
-    ))
+    let names = [
+        "intr",
+        "ctxt",
+        "softirq",
+        "processes",
+        "procs_running",
+        "procs_blocked",
+    ];
+    let missing = matched.iter().position(|m| !*m).unwrap_or(0);
+    Err(Error::MissingField(names[missing]))
+}
+
+/// Get basic [CpuStats] info the host.
+///
+/// It only contains raw information, to get the delta you need
+/// to get the diff between N and N-1.
+///
+/// [CpuStats]: ../cpu/struct.CpuStats.html
+pub fn get_cpustats() -> Result<CpuStats, Error> {
+    get_cpustats_with_root(&DEFAULT_ROOT)
+}
+
+/// Get basic [CpuStats] info the host reading from the given [SysRoot].
+///
+/// [CpuStats]: ../cpu/struct.CpuStats.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cpustats_with_root(root: &SysRoot) -> Result<CpuStats, Error> {
+    let file = File::open(root.proc_path("stat"))?;
+    parse_cpustats(BufReader::with_capacity(1024, file))
 }
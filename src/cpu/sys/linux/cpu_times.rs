@@ -1,10 +1,117 @@
 use crate::cpu::CpuTimes;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
 
 use std::{
     fs::File,
-    io::{prelude::*, BufReader, Error, ErrorKind},
+    io::{prelude::*, BufReader},
 };
 
+/// Parse a `cpu`/`cpuN` line (at `lineno`) of /proc/stat into a [CpuTimes].
+///
+/// The first column (the name of the stats) is skipped.
+fn parse_cputimes_line(line: &str, lineno: usize) -> Result<CpuTimes, Error> {
+    // Split whitespaces and get an Array of values
+    let mut fields = line.split_whitespace();
+    let parse = |value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|_| Error::parse("/proc/stat", lineno))
+    };
+
+    // Skip the first columns which is the name of the stats
+    let user = nth!(fields, 1, "user")?;
+    let nice = nth!(fields, 0, "nice")?;
+    let system = nth!(fields, 0, "system")?;
+    let idle = nth!(fields, 0, "idle")?;
+    let iowait = nth!(fields, 0, "iowait")?;
+    let irq = nth!(fields, 0, "irq")?;
+    let softirq = nth!(fields, 0, "softirq")?;
+    // Unwrap_or because the 8th-10th fields are not present on old kernel
+    let steal = nth!(fields, 0, "steal").unwrap_or("0");
+    let guest = nth!(fields, 0, "guest").unwrap_or("0");
+    let guest_nice = nth!(fields, 0, "guest_nice").unwrap_or("0");
+    // Return the struct, and parse to u64
+    Ok(CpuTimes {
+        user: parse(user)?,
+        nice: parse(nice)?,
+        system: parse(system)?,
+        idle: parse(idle)?,
+        iowait: parse(iowait)?,
+        irq: parse(irq)?,
+        softirq: parse(softirq)?,
+        steal: parse(steal)?,
+        guest: parse(guest)?,
+        guest_nice: parse(guest_nice)?,
+    })
+}
+
+/// Parse the aggregated [CpuTimes] from the content of /proc/stat.
+///
+/// [CpuTimes]: ../cpu/struct.CpuTimes.html
+pub fn parse_cputimes<R: BufRead>(mut reader: R) -> Result<CpuTimes, Error> {
+    let mut lineno = 0;
+    let mut line = String::with_capacity(128);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        // The aggregated line is the first one, but be tolerant
+        if line.starts_with("cpu ") {
+            return parse_cputimes_line(&line, lineno);
+        }
+        line.clear();
+    }
+
+    Err(Error::MissingField("cpu"))
+}
+
+/// Parse the per-cpu [CpuTimes] from the content of /proc/stat.
+///
+/// See [get_cputimes_percpu] for the layout of the returned Vec.
+///
+/// [CpuTimes]: ../cpu/struct.CpuTimes.html
+/// [get_cputimes_percpu]: ../cpu/fn.get_cputimes_percpu.html
+pub fn parse_cputimes_percpu<R: BufRead>(mut reader: R) -> Result<Vec<CpuTimes>, Error> {
+    let mut v_cputimes: Vec<CpuTimes> = Vec::new();
+    let mut lineno = 0;
+    let mut line = String::with_capacity(128);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        // Per-cpu lines are all grouped right after the aggregated `cpu` one
+        if !line.starts_with("cpu") {
+            if !v_cputimes.is_empty() {
+                break;
+            }
+            line.clear();
+            continue;
+        }
+
+        // Extract the CPU id, the aggregated `cpu` line doesn't have any
+        let id = match line
+            .split_whitespace()
+            .next()
+            .map(|name| name[3..].parse::<usize>())
+        {
+            Some(Ok(id)) => id,
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+
+        let cputimes = parse_cputimes_line(&line, lineno)?;
+        if id >= v_cputimes.len() {
+            v_cputimes.resize(id + 1, CpuTimes::default());
+        }
+        v_cputimes[id] = cputimes;
+        line.clear();
+    }
+
+    if v_cputimes.is_empty() {
+        return Err(Error::MissingField("cpuN"));
+    }
+
+    Ok(v_cputimes)
+}
+
 /// Get basic [CpuTimes] info the host.
 ///
 /// It only contains raw information, to get the delta we need
@@ -12,40 +119,36 @@ use std::{
 ///
 /// [CpuTimes]: ../cpu/struct.CpuTimes.html
 pub fn get_cputimes() -> Result<CpuTimes, Error> {
-    let file = File::open("/proc/stat")?;
-    let mut file = BufReader::with_capacity(1024, file);
+    get_cputimes_with_root(&DEFAULT_ROOT)
+}
 
-    let mut line = String::with_capacity(128);
-    if file.read_line(&mut line)? != 0 {
-        // Split whitespaces and get an Array of values
-        let mut fields = line.split_whitespace();
-
-        // Skip the first columns which is the name of the stats
-        let user = nth!(fields, 1)?;
-        let nice = nth!(fields, 0)?;
-        let system = nth!(fields, 0)?;
-        let idle = nth!(fields, 0)?;
-        let iowait = nth!(fields, 0)?;
-        let irq = nth!(fields, 0)?;
-        let softirq = nth!(fields, 0)?;
-        // Unwrap_or because the 8th-10th fields are not present on old kernel
-        let steal = nth!(fields, 0).unwrap_or("0");
-        let guest = nth!(fields, 0).unwrap_or("0");
-        let guest_nice = nth!(fields, 0).unwrap_or("0");
-        // Return the struct, and parse to i64
-        return Ok(CpuTimes {
-            user: user.parse::<u64>().unwrap(),
-            nice: nice.parse::<u64>().unwrap(),
-            system: system.parse::<u64>().unwrap(),
-            idle: idle.parse::<u64>().unwrap(),
-            iowait: iowait.parse::<u64>().unwrap(),
-            irq: irq.parse::<u64>().unwrap(),
-            softirq: softirq.parse::<u64>().unwrap(),
-            steal: steal.parse::<u64>().unwrap(),
-            guest: guest.parse::<u64>().unwrap(),
-            guest_nice: guest_nice.parse::<u64>().unwrap(),
-        });
-    }
+/// Get basic [CpuTimes] info the host reading from the given [SysRoot].
+///
+/// [CpuTimes]: ../cpu/struct.CpuTimes.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cputimes_with_root(root: &SysRoot) -> Result<CpuTimes, Error> {
+    let file = File::open(root.proc_path("stat"))?;
+    parse_cputimes(BufReader::with_capacity(1024, file))
+}
 
-    Err(Error::new(ErrorKind::Other, "Couldn't get the CpuTimes"))
+/// Get basic [CpuTimes] info for each CPU of the host.
+///
+/// The Vec is indexed by the CPU id (`cpuN` in /proc/stat). Offline CPUs
+/// are not listed by the kernel, their slot is filled with a zeroed [CpuTimes].
+///
+/// It only contains raw information, to get the delta we need
+/// to get the diff between N and N-1.
+///
+/// [CpuTimes]: ../cpu/struct.CpuTimes.html
+pub fn get_cputimes_percpu() -> Result<Vec<CpuTimes>, Error> {
+    get_cputimes_percpu_with_root(&DEFAULT_ROOT)
+}
+
+/// Get basic [CpuTimes] info for each CPU of the host reading from the given [SysRoot].
+///
+/// [CpuTimes]: ../cpu/struct.CpuTimes.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_cputimes_percpu_with_root(root: &SysRoot) -> Result<Vec<CpuTimes>, Error> {
+    let file = File::open(root.proc_path("stat"))?;
+    parse_cputimes_percpu(BufReader::with_capacity(4096, file))
 }
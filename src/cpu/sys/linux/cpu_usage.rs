@@ -0,0 +1,62 @@
+use crate::cpu::{self, CpuTimes, CpuUsage, CpuUsageSample};
+use crate::{Error, SysRoot};
+
+/// Stateful sampler computing the [CpuUsage] between each call to [sample].
+///
+/// It keeps the previous aggregated and per-cpu [CpuTimes] snapshots so
+/// the caller doesn't have to diff them by hand.
+///
+/// [CpuUsage]: ../cpu/struct.CpuUsage.html
+/// [CpuTimes]: ../cpu/struct.CpuTimes.html
+/// [sample]: #method.sample
+#[derive(Debug, Clone)]
+pub struct CpuUsageSampler {
+    root: SysRoot,
+    prev: CpuTimes,
+    prev_percpu: Vec<CpuTimes>,
+}
+
+impl CpuUsageSampler {
+    /// Create a new sampler and take the initial snapshot.
+    pub fn new() -> Result<Self, Error> {
+        Self::with_root(SysRoot::default())
+    }
+
+    /// Create a new sampler reading from the given [SysRoot] and take the initial snapshot.
+    ///
+    /// [SysRoot]: ../struct.SysRoot.html
+    pub fn with_root(root: SysRoot) -> Result<Self, Error> {
+        Ok(CpuUsageSampler {
+            prev: cpu::get_cputimes_with_root(&root)?,
+            prev_percpu: cpu::get_cputimes_percpu_with_root(&root)?,
+            root,
+        })
+    }
+
+    /// Take a new snapshot and return the [CpuUsageSample] since the previous one.
+    ///
+    /// [CpuUsageSample]: ../cpu/struct.CpuUsageSample.html
+    pub fn sample(&mut self) -> Result<CpuUsageSample, Error> {
+        let curr = cpu::get_cputimes_with_root(&self.root)?;
+        let curr_percpu = cpu::get_cputimes_percpu_with_root(&self.root)?;
+
+        let total = CpuUsage::from_delta(&self.prev, &curr);
+        // A cpu that was offline has zeroed CpuTimes and will yield None,
+        // same for a cpu which just appeared (hotplug).
+        let percpu = curr_percpu
+            .iter()
+            .enumerate()
+            .map(|(id, curr)| match self.prev_percpu.get(id) {
+                Some(prev) if prev.total_time() != 0 && curr.total_time() != 0 => {
+                    CpuUsage::from_delta(prev, curr)
+                }
+                _ => None,
+            })
+            .collect();
+
+        self.prev = curr;
+        self.prev_percpu = curr_percpu;
+
+        Ok(CpuUsageSample { total, percpu })
+    }
+}
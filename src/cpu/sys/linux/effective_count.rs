@@ -0,0 +1,41 @@
+use crate::cgroup::get_cgroup_cpu_limit_with_root;
+use crate::cpu::get_logical_count;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+/// Return the number of cpus the current process can run on (sched_getaffinity).
+fn affinity_count() -> Result<u32, Error> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    if unsafe { libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) } != 0
+    {
+        return Err(Error::last_os_error());
+    }
+
+    Ok(unsafe { libc::CPU_COUNT(&set) } as u32)
+}
+
+/// Return the number of cpus the current process can effectively use.
+///
+/// It's the minimum of the online cpus, the cpus allowed by the affinity mask and
+/// the cgroup cpu bandwidth limit (cpu.max or cpu.cfs_quota_us/cpu.cfs_period_us, rounded up)
+/// which is what a container is given. Useful to size a thread pool.
+pub fn get_effective_cpu_count() -> Result<u32, Error> {
+    get_effective_cpu_count_with_root(&DEFAULT_ROOT)
+}
+
+/// Return the number of cpus the current process can effectively use,
+/// reading the cgroup limits from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_effective_cpu_count_with_root(root: &SysRoot) -> Result<u32, Error> {
+    let mut count = get_logical_count()?;
+    // Restricted by taskset, cpuset, ...
+    if let Ok(affinity) = affinity_count() {
+        count = count.min(affinity);
+    }
+    // Not being in a cgroup (or not being able to read it) means no limit
+    if let Ok(Some(limit)) = get_cgroup_cpu_limit_with_root(root) {
+        count = count.min(limit.ceil() as u32);
+    }
+
+    Ok(count.max(1))
+}
@@ -1,4 +1,4 @@
-use std::io::Error;
+use crate::Error;
 
 /// Return the number of logical core the system has.
 pub fn get_logical_count() -> Result<u32, Error> {
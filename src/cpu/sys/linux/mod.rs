@@ -1,11 +1,15 @@
 mod cpu_freq;
 mod cpu_stats;
 mod cpu_times;
+mod cpu_usage;
+mod effective_count;
 mod logical_count;
 mod physical_count;
 
 pub use cpu_freq::*;
 pub use cpu_stats::*;
 pub use cpu_times::*;
+pub use cpu_usage::*;
+pub use effective_count::*;
 pub use logical_count::*;
 pub use physical_count::*;
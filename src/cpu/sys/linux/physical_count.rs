@@ -1,23 +1,24 @@
-use crate::read_and_trim;
+use crate::{read_and_trim, Error, SysRoot, DEFAULT_ROOT};
 
 use std::collections::HashSet;
 use std::{
     fs::File,
-    io::{prelude::*, BufReader, Error, ErrorKind},
+    io::{prelude::*, BufReader, ErrorKind},
 };
 
 /// Return the number of physical core the system has using topology (glob).
-fn get_from_glob() -> Result<u32, Error> {
-    let path = "/sys/devices/system/cpu/cpu*/topology/core_id";
-    let entries = glob::glob(path).expect("Invalid glob pattern");
+fn get_from_glob(root: &SysRoot) -> Result<u32, Error> {
+    let path = root.sys_path("devices/system/cpu/cpu*/topology/core_id");
+    let entries = glob::glob(&path.to_string_lossy())
+        .map_err(|e| std::io::Error::new(ErrorKind::InvalidInput, e))?;
     let mut acc = HashSet::<u32>::new();
 
     for entry in entries {
         let entry = entry.map_err(|e| e.into_error())?;
         // Read the content & trim the result and parse to u32
-        match read_and_trim(entry)?.parse() {
+        match read_and_trim(&entry)?.parse() {
             Ok(val) => acc.insert(val),
-            Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
+            Err(_) => return Err(Error::parse(entry.to_string_lossy(), 1)),
         };
     }
 
@@ -26,40 +27,40 @@ fn get_from_glob() -> Result<u32, Error> {
     if !acc.is_empty() {
         Ok(acc.len() as u32)
     } else {
-        Err(Error::from(ErrorKind::NotFound))
+        Err(Error::MissingField("core_id"))
     }
 }
 
 /// Return the number of physical core the system has using /proc/cpuinfo.
-fn get_from_cpuinfo() -> Result<u32, Error> {
-    let file = File::open("/proc/cpuinfo")?;
+fn get_from_cpuinfo(root: &SysRoot) -> Result<u32, Error> {
+    let file = File::open(root.proc_path("cpuinfo"))?;
     let mut file = BufReader::with_capacity(1024, file);
 
     let mut line = String::with_capacity(256);
     while file.read_line(&mut line)? != 0 {
-        let lenght = line.len();
-        if lenght > 12 && lenght < 24 && &line[..9] == "cpu cores" {
-            match line[12..lenght - 1].parse::<u32>() {
-                Ok(val) => return Ok(val),
-                Err(_) => {
-                    line.clear();
-                    continue;
-                }
-            };
+        // Lines are in the form of `cpu cores\t: 4`
+        if line.starts_with("cpu cores") {
+            if let Some(Ok(val)) = line.split(':').nth(1).map(|v| v.trim().parse::<u32>()) {
+                return Ok(val);
+            }
         }
         line.clear();
     }
 
-    Err(Error::new(
-        ErrorKind::Other,
-        "Cannot determine the number of physical core",
-    ))
+    Err(Error::MissingField("cpu cores"))
 }
 
 /// Return the number of physical core the system has.
 pub fn get_physical_count() -> Result<u32, Error> {
-    match get_from_glob() {
+    get_physical_count_with_root(&DEFAULT_ROOT)
+}
+
+/// Return the number of physical core the system has reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_physical_count_with_root(root: &SysRoot) -> Result<u32, Error> {
+    match get_from_glob(root) {
         Ok(val) => Ok(val),
-        Err(..) => get_from_cpuinfo(),
+        Err(..) => get_from_cpuinfo(root),
     }
 }
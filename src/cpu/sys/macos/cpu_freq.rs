@@ -1,5 +1,6 @@
+use crate::Error;
+
 use libc::{c_uint, c_void, sysctl};
-use std::io::Error;
 
 /// Get the cpufreq as f64 (in MHz).
 pub fn get_cpufreq() -> Result<f64, Error> {
@@ -1,10 +1,10 @@
 use crate::binding::{host_statistics, mach_host_self, vmmeter};
 use crate::cpu::CpuStats;
+use crate::Error;
 
 use mach::mach_port::mach_port_deallocate;
 use mach::traps::mach_task_self;
 use mach::vm_types::integer_t;
-use std::io::Error;
 
 /// Get basic [CpuStats] info the host.
 ///
@@ -1,10 +1,10 @@
 use crate::binding::{host_statistics64, mach_host_self};
 use crate::cpu::{host_cpu_load_info, CpuTimes};
+use crate::Error;
 
 use mach::mach_port::mach_port_deallocate;
 use mach::traps::mach_task_self;
 use mach::vm_types::integer_t;
-use std::io::Error;
 
 /// Get basic [CpuTimes] info the host.
 ///
@@ -1,5 +1,6 @@
+use crate::Error;
+
 use libc::{c_uint, c_void, sysctl};
-use std::io::Error;
 
 /// Return the number of physical core the system has.
 pub fn get_physical_count() -> Result<u32, Error> {
@@ -1,7 +1,7 @@
 use crate::cpu::LoadAvg;
+use crate::Error;
 
 use libc::{c_double, getloadavg};
-use std::io::Error;
 
 /// Returns the [LoadAvg] over the last 1, 5 and 15 minutes.
 ///
@@ -2,49 +2,279 @@ mod sys;
 
 pub use sys::*;
 
+use crate::Error;
 use libc::statvfs;
 use serde::Serialize;
 use std::ffi::CString;
-use std::io::Error;
 
 /// Struct containing a disk' information.
 #[derive(Debug, Clone, Serialize)]
 pub struct Disks {
     pub name: String,
     pub mount_point: String,
+    pub fs_type: String,
+    /// Mount options (eg: rw,relatime), always empty on macOS
+    pub options: Vec<String>,
+    pub read_only: bool,
     /// Value is in MB
     pub total_space: u64,
     /// Value is in MB
     pub avail_space: u64,
+    /// Value is in MB
+    pub used_space: u64,
+    pub inodes_total: u64,
+    pub inodes_free: u64,
+    pub inodes_used: u64,
+}
+
+/// Struct containing a mount entry (as listed in /proc/mounts).
+#[derive(Debug, Clone, Serialize)]
+pub struct Mount {
+    pub name: String,
+    pub mount_point: String,
+    pub fs_type: String,
+    pub options: Vec<String>,
+}
+
+/// Struct containing a mount entry as listed in /proc/self/mountinfo.
+///
+/// The escaped characters (eg: `\040` for a space) of the paths are decoded.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct MountInfo {
+    pub mount_id: u32,
+    pub parent_id: u32,
+    pub major: u32,
+    pub minor: u32,
+    /// Root of the mount within the filesystem (not / for a bind mount of a directory)
+    pub root: String,
+    pub mount_point: String,
+    /// Per-mount options (eg: rw,relatime)
+    pub mount_options: Vec<String>,
+    /// Propagation flags (eg: shared:1, master:5 or unbindable)
+    pub propagation: Vec<String>,
+    pub fs_type: String,
+    /// Mount source (eg: /dev/sda1)
+    pub source: String,
+    /// Per-superblock options (eg: rw,errors=remount-ro)
+    pub super_options: Vec<String>,
 }
 
 /// Struct containing a disk_io (bytes read/wrtn) information.
+///
+/// All times are in milliseconds (see the kernel's Documentation/admin-guide/iostats.rst).
+/// The merged, in flight and weighted time values are always 0 on macOS.
 #[derive(Debug, Clone, Serialize)]
 pub struct IoBlock {
+    pub major: u32,
+    pub minor: u32,
     pub device_name: String,
     pub read_count: u64,
+    pub read_merged: u64,
     pub read_bytes: u64,
+    /// Time spent reading (in ms)
+    pub read_time: u64,
     pub write_count: u64,
+    pub write_merged: u64,
     pub write_bytes: u64,
+    /// Time spent writing (in ms)
+    pub write_time: u64,
+    /// Number of I/Os currently in progress
+    pub in_flight: u64,
     pub busy_time: u64,
+    /// Time spent doing I/Os weighted by the number of I/Os in progress
+    pub weighted_time: u64,
+    /// Only present since Linux 4.18
+    pub discard_count: Option<u64>,
+    pub discard_merged: Option<u64>,
+    pub discard_bytes: Option<u64>,
+    pub discard_time: Option<u64>,
+    /// Only present since Linux 5.5
+    pub flush_count: Option<u64>,
+    pub flush_time: Option<u64>,
 }
 
-/// Return the (total, free) space of a Disk from it's path (mount_point).
-pub fn disk_usage<P>(path: P) -> Result<(u64, u64), Error>
+/// Struct containing the information of a block device (disk or partition) from /sys/block.
+///
+/// The hardware properties of a partition are the ones of its parent disk.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockDevice {
+    pub name: String,
+    pub major: u32,
+    pub minor: u32,
+    /// Name of the disk of a partition, None for a disk
+    pub parent: Option<String>,
+    /// Value is in bytes
+    pub size: u64,
+    /// Value is in bytes, None if the device doesn't report it
+    pub logical_sector_size: Option<u64>,
+    /// Value is in bytes, None if the device doesn't report it
+    pub physical_sector_size: Option<u64>,
+    pub rotational: Option<bool>,
+    pub removable: bool,
+    pub read_only: bool,
+    pub model: Option<String>,
+    pub vendor: Option<String>,
+    pub serial: Option<String>,
+    /// The active I/O scheduler (eg: mq-deadline), None if there's none
+    pub scheduler: Option<String>,
+}
+
+/// Struct containing the I/O rates of a disk between two [IoBlock] snapshots.
+///
+/// `read_await`/`write_await` are the average time (in ms) of the I/O requests
+/// (None if there was none), `util` is the percentage of time the device was busy.
+///
+/// [IoBlock]: ../disks/struct.IoBlock.html
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct IoBlockRate {
+    pub device_name: String,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    /// Bytes read per second
+    pub read_throughput: f64,
+    /// Bytes written per second
+    pub write_throughput: f64,
+    pub read_await: Option<f64>,
+    pub write_await: Option<f64>,
+    pub util: f64,
+}
+
+#[cfg(target_os = "linux")]
+impl IoBlockRate {
+    /// Compute the [IoBlockRate] between two snapshots of the same device taken `elapsed` apart.
+    ///
+    /// The counters are `unsigned long` in the kernel, a counter going backward from
+    /// close to `u32::MAX` to close to 0 is considered as having wrapped around at 32 bits.
+    /// Return None if any other counter went backward (the device was re-created or its
+    /// counters reset).
+    ///
+    /// [IoBlockRate]: ../disks/struct.IoBlockRate.html
+    pub fn from_delta(
+        prev: &IoBlock,
+        curr: &IoBlock,
+        elapsed: std::time::Duration,
+    ) -> Option<IoBlockRate> {
+        // The bytes are counted in sectors by the kernel
+        const SECTOR: u64 = 512;
+        // A wrapping 32 bits counter goes from the last quarter of its range to the first
+        // one, any other decrease is a reset (or another device under the same name)
+        const QUARTER: u64 = (u32::MAX >> 2) as u64;
+        let delta = |prev: u64, curr: u64| match curr.checked_sub(prev) {
+            Some(delta) => Some(delta),
+            None if prev > u32::MAX as u64 - QUARTER
+                && prev <= u32::MAX as u64
+                && curr <= QUARTER =>
+            {
+                Some(curr + (u32::MAX as u64 + 1) - prev)
+            }
+            None => None,
+        };
+        let secs = elapsed.as_secs_f64();
+        let per_sec = |delta: u64| match secs > 0.0 {
+            true => delta as f64 / secs,
+            false => 0.0,
+        };
+        let await_time = |time: u64, count: u64| match count {
+            0 => None,
+            count => Some(time as f64 / count as f64),
+        };
+
+        let read_count = delta(prev.read_count, curr.read_count)?;
+        let write_count = delta(prev.write_count, curr.write_count)?;
+        let read_bytes = delta(prev.read_bytes / SECTOR, curr.read_bytes / SECTOR)? * SECTOR;
+        let write_bytes = delta(prev.write_bytes / SECTOR, curr.write_bytes / SECTOR)? * SECTOR;
+        let read_time = delta(prev.read_time, curr.read_time)?;
+        let write_time = delta(prev.write_time, curr.write_time)?;
+        let busy_time = delta(prev.busy_time, curr.busy_time)?;
+
+        Some(IoBlockRate {
+            device_name: curr.device_name.clone(),
+            read_iops: per_sec(read_count),
+            write_iops: per_sec(write_count),
+            read_throughput: per_sec(read_bytes),
+            write_throughput: per_sec(write_bytes),
+            read_await: await_time(read_time, read_count),
+            write_await: await_time(write_time, write_count),
+            // busy_time is in ms
+            util: (per_sec(busy_time) / 10.0).min(100.0),
+        })
+    }
+}
+
+/// Struct containing the [IoBlockRate] of each device as returned by an [IoBlockSampler],
+/// along with the devices which were added or removed since the previous sample.
+///
+/// The added devices and those whose counters were reset don't have any [IoBlockRate]
+/// until the next sample.
+///
+/// [IoBlockRate]: ../disks/struct.IoBlockRate.html
+/// [IoBlockSampler]: ../disks/struct.IoBlockSampler.html
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct IoBlockSample {
+    pub ioblocks: Vec<IoBlockRate>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub reset: Vec<String>,
+}
+
+/// Struct containing the usage of a filesystem (as returned by statvfs).
+///
+/// Space values are in bytes, `avail` being the space available to unprivileged users.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct DiskUsage {
+    pub total: u64,
+    pub avail: u64,
+    pub used: u64,
+    pub inodes_total: u64,
+    pub inodes_free: u64,
+    pub inodes_used: u64,
+    pub read_only: bool,
+}
+
+/// Return the [DiskUsage] of the filesystem mounted at `path`.
+///
+/// [DiskUsage]: ../disks/struct.DiskUsage.html
+#[allow(clippy::unnecessary_cast)]
+pub fn get_disk_usage<P>(path: P) -> Result<DiskUsage, Error>
 where
     P: AsRef<[u8]>,
 {
     let mut statvfs = std::mem::MaybeUninit::<statvfs>::uninit();
+    let path = CString::new(path.as_ref()).map_err(std::io::Error::from)?;
 
-    if unsafe { libc::statvfs(CString::new(path.as_ref())?.as_ptr(), statvfs.as_mut_ptr()) } == -1 {
+    if unsafe { libc::statvfs(path.as_ptr(), statvfs.as_mut_ptr()) } == -1 {
         return Err(Error::last_os_error());
     }
 
     let statvfs = unsafe { statvfs.assume_init() };
-    let total = statvfs.f_blocks as u64 * statvfs.f_frsize as u64;
-    let free = statvfs.f_bavail as u64 * statvfs.f_frsize as u64;
+    let frsize = statvfs.f_frsize as u64;
+    let inodes_total = statvfs.f_files as u64;
+    let inodes_free = statvfs.f_ffree as u64;
+
+    Ok(DiskUsage {
+        total: statvfs.f_blocks as u64 * frsize,
+        avail: statvfs.f_bavail as u64 * frsize,
+        // Like df, the reserved blocks are not counted as used
+        used: (statvfs.f_blocks as u64).saturating_sub(statvfs.f_bfree as u64) * frsize,
+        inodes_total,
+        inodes_free,
+        inodes_used: inodes_total.saturating_sub(inodes_free),
+        read_only: statvfs.f_flag as u64 & libc::ST_RDONLY as u64 != 0,
+    })
+}
+
+/// Return the (total, free) space of a Disk from it's path (mount_point).
+pub fn disk_usage<P>(path: P) -> Result<(u64, u64), Error>
+where
+    P: AsRef<[u8]>,
+{
+    let usage = get_disk_usage(path)?;
 
-    Ok((total, free))
+    Ok((usage.total, usage.avail))
 }
 
 /// Detect if a filesystem is for a physical drive or not.
@@ -0,0 +1,181 @@
+use crate::disks::BlockDevice;
+use crate::{read_and_trim, Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+// The size file is always in 512 bytes sectors, whatever the sector size of the device.
+const SIZE_SECTOR: u64 = 512;
+
+/// Read the trimmed content of `path` or return None if it doesn't exist or is empty.
+fn read_optional(path: PathBuf) -> Result<Option<String>, Error> {
+    match read_and_trim(path) {
+        Ok(content) if content.is_empty() => Ok(None),
+        Ok(content) => Ok(Some(content)),
+        Err(Error::Io(err)) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Read a numerical value from the file `name` of the device `dir`.
+fn read_value(dir: &Path, name: &str) -> Result<u64, Error> {
+    read_and_trim(dir.join(name))?
+        .parse::<u64>()
+        .map_err(|_| Error::parse(dir.join(name).to_string_lossy(), 1))
+}
+
+/// Read a numerical value from the file `name` of the device `dir`, None if it doesn't exist.
+fn read_optional_value(dir: &Path, name: &str) -> Result<Option<u64>, Error> {
+    match read_optional(dir.join(name))? {
+        Some(value) => value
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|_| Error::parse(dir.join(name).to_string_lossy(), 1)),
+        None => Ok(None),
+    }
+}
+
+/// Return true if the error means the device was removed while we were reading it.
+fn is_gone(err: &Error) -> bool {
+    match err {
+        Error::Io(err) => err.kind() == ErrorKind::NotFound,
+        _ => false,
+    }
+}
+
+/// Parse the major and minor numbers from the dev file (`8:0`).
+fn read_dev(dir: &Path) -> Result<(u32, u32), Error> {
+    let dev = read_and_trim(dir.join("dev"))?;
+    let err = || Error::parse(dir.join("dev").to_string_lossy(), 1);
+
+    let mut parts = dev.splitn(2, ':');
+    let major = parts.next().and_then(|major| major.parse::<u32>().ok());
+    let minor = parts.next().and_then(|minor| minor.parse::<u32>().ok());
+    match (major, minor) {
+        (Some(major), Some(minor)) => Ok((major, minor)),
+        _ => Err(err()),
+    }
+}
+
+/// Return the active scheduler from the content of queue/scheduler (`noop deadline [cfq]`).
+fn parse_scheduler(content: &str) -> Option<String> {
+    content
+        .split_whitespace()
+        .find(|scheduler| scheduler.starts_with('['))
+        .map(|scheduler| scheduler.trim_matches(|c| c == '[' || c == ']'))
+        .filter(|scheduler| *scheduler != "none")
+        .map(str::to_owned)
+}
+
+/// Read the [BlockDevice] of the disk `name` located at `dir`.
+fn read_disk(dir: &Path, name: String) -> Result<BlockDevice, Error> {
+    let (major, minor) = read_dev(dir)?;
+    let device = dir.join("device");
+
+    Ok(BlockDevice {
+        name,
+        major,
+        minor,
+        parent: None,
+        size: read_value(dir, "size")? * SIZE_SECTOR,
+        // Some virtual devices don't have all the queue attributes
+        logical_sector_size: read_optional_value(dir, "queue/logical_block_size")?,
+        physical_sector_size: read_optional_value(dir, "queue/physical_block_size")?,
+        rotational: read_optional_value(dir, "queue/rotational")?.map(|rotational| rotational == 1),
+        removable: read_value(dir, "removable")? == 1,
+        read_only: read_value(dir, "ro")? == 1,
+        // MMC devices only have a name
+        model: match read_optional(device.join("model"))? {
+            Some(model) => Some(model),
+            None => read_optional(device.join("name"))?,
+        },
+        vendor: read_optional(device.join("vendor"))?,
+        serial: read_optional(device.join("serial"))?,
+        scheduler: read_optional(dir.join("queue/scheduler"))?
+            .and_then(|scheduler| parse_scheduler(&scheduler)),
+    })
+}
+
+/// Read the [BlockDevice] of the partition located at `dir` of the `disk`.
+fn read_partition(dir: &Path, disk: &BlockDevice) -> Result<BlockDevice, Error> {
+    let (major, minor) = read_dev(dir)?;
+
+    Ok(BlockDevice {
+        name: dir
+            .file_name()
+            .map(|name| name.to_string_lossy().replace('!', "/"))
+            .unwrap_or_default(),
+        major,
+        minor,
+        parent: Some(disk.name.clone()),
+        size: read_value(dir, "size")? * SIZE_SECTOR,
+        read_only: read_value(dir, "ro")? == 1,
+        ..disk.clone()
+    })
+}
+
+/// Return the `disk` followed by its partitions located in its directory `dir`.
+///
+/// The partitions removed while being read are skipped.
+fn read_partitions(dir: &Path, disk: BlockDevice) -> Result<Vec<BlockDevice>, Error> {
+    // The partitions are the subdirectories with a partition file
+    let mut partitions: Vec<BlockDevice> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let part_dir = entry?.path();
+        if !part_dir.join("partition").exists() {
+            continue;
+        }
+
+        match read_partition(&part_dir, &disk) {
+            Ok(partition) => partitions.push(partition),
+            Err(err) if is_gone(&err) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    partitions.sort_unstable_by_key(|partition| partition.minor);
+
+    let mut devices = Vec::with_capacity(partitions.len() + 1);
+    devices.push(disk);
+    devices.append(&mut partitions);
+
+    Ok(devices)
+}
+
+/// Get the [BlockDevice] of each disk and of its partitions listed in /sys/block.
+///
+/// The partitions are listed right after their disk, the devices removed while
+/// being read are skipped.
+///
+/// [BlockDevice]: ../disks/struct.BlockDevice.html
+pub fn get_block_devices() -> Result<Vec<BlockDevice>, Error> {
+    get_block_devices_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the [BlockDevice] of each disk and of its partitions reading from the given [SysRoot].
+///
+/// [BlockDevice]: ../disks/struct.BlockDevice.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_block_devices_with_root(root: &SysRoot) -> Result<Vec<BlockDevice>, Error> {
+    let mut disks: Vec<(String, PathBuf)> = Vec::new();
+    for entry in fs::read_dir(root.sys_path("block"))? {
+        let entry = entry?;
+        // Some devices have a slash in their name (eg. cciss/c0d0) replaced by `!` in sysfs
+        let name = entry.file_name().to_string_lossy().replace('!', "/");
+        disks.push((name, entry.path()));
+    }
+    disks.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+    let mut v_devices: Vec<BlockDevice> = Vec::with_capacity(disks.len());
+    for (name, dir) in disks {
+        match read_disk(&dir, name).and_then(|disk| read_partitions(&dir, disk)) {
+            Ok(mut devices) => v_devices.append(&mut devices),
+            Err(err) if is_gone(&err) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(v_devices)
+}
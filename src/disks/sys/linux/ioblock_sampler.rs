@@ -0,0 +1,83 @@
+use crate::disks::{self, IoBlock, IoBlockRate, IoBlockSample};
+use crate::{Error, SysRoot};
+
+use std::{collections::HashMap, time::Instant};
+
+/// Stateful sampler computing the [IoBlockRate] of each disk/partition between each call to [sample].
+///
+/// It keeps the previous [IoBlock] of each device, keyed by its `device_name`.
+///
+/// [IoBlockRate]: ../disks/struct.IoBlockRate.html
+/// [IoBlock]: ../disks/struct.IoBlock.html
+/// [sample]: #method.sample
+#[derive(Debug, Clone)]
+pub struct IoBlockSampler {
+    root: SysRoot,
+    prev: HashMap<String, IoBlock>,
+    prev_instant: Instant,
+}
+
+impl IoBlockSampler {
+    /// Create a new sampler and take the initial snapshot.
+    pub fn new() -> Result<Self, Error> {
+        Self::with_root(SysRoot::default())
+    }
+
+    /// Create a new sampler reading from the given [SysRoot] and take the initial snapshot.
+    ///
+    /// [SysRoot]: ../struct.SysRoot.html
+    pub fn with_root(root: SysRoot) -> Result<Self, Error> {
+        let prev = disks::get_ioblocks_with_root(&root)?
+            .into_iter()
+            .map(|ioblock| (ioblock.device_name.clone(), ioblock))
+            .collect();
+
+        Ok(IoBlockSampler {
+            prev,
+            prev_instant: Instant::now(),
+            root,
+        })
+    }
+
+    /// Take a new snapshot and return the [IoBlockSample] since the previous one.
+    ///
+    /// [IoBlockSample]: ../disks/struct.IoBlockSample.html
+    pub fn sample(&mut self) -> Result<IoBlockSample, Error> {
+        let ioblocks = disks::get_ioblocks_with_root(&self.root)?;
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.prev_instant);
+
+        let mut curr: HashMap<String, IoBlock> = HashMap::with_capacity(ioblocks.len());
+        let mut added: Vec<String> = Vec::new();
+        let mut reset: Vec<String> = Vec::new();
+        let mut rates: Vec<IoBlockRate> = Vec::with_capacity(ioblocks.len());
+        for ioblock in ioblocks {
+            match self.prev.get(&ioblock.device_name) {
+                Some(prev) => match IoBlockRate::from_delta(prev, &ioblock, elapsed) {
+                    Some(rate) => rates.push(rate),
+                    None => reset.push(ioblock.device_name.clone()),
+                },
+                None => added.push(ioblock.device_name.clone()),
+            }
+            curr.insert(ioblock.device_name.clone(), ioblock);
+        }
+
+        let mut removed: Vec<String> = self
+            .prev
+            .keys()
+            .filter(|name| !curr.contains_key(*name))
+            .cloned()
+            .collect();
+        removed.sort_unstable();
+
+        self.prev = curr;
+        self.prev_instant = now;
+
+        Ok(IoBlockSample {
+            ioblocks: rates,
+            added,
+            removed,
+            reset,
+        })
+    }
+}
@@ -1,55 +1,83 @@
 use crate::disks::IoBlock;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
 
-use std::io::{Error, ErrorKind};
 use std::{
     fs::File,
     io::{BufRead, BufReader},
-    path::Path,
 };
 
-// https://github.com/heim-rs/heim/blob/ad691385940babcab857b1e19ebe95af35b8d70e/heim-disk/src/sys/linux/counters.rs#L21
-// Magic value shared accross almost all Linux source code
-// Despite this value can be queried at runtime
-// via /sys/block/{DISK}/queue/hw_sector_size and results may vary
-// between 1k, 2k, or 4k... 512 appears to be a magic constant used.
+// The kernel always counts the sectors of /proc/diskstats in 512 bytes units (SECTOR_SHIFT),
+// whatever the hardware sector size of the device (see BlockDevice for it).
 const DISK_SECTOR_SIZE: u64 = 512;
 
-#[inline]
-fn _get_ioblocks(physical: bool) -> Result<Vec<IoBlock>, Error> {
-    let file = File::open("/proc/diskstats")?;
+/// Parse the [IoBlock] of each disks/partitions from the content of /proc/diskstats.
+///
+/// [IoBlock]: ../disks/struct.IoBlock.html
+pub fn parse_ioblocks<R: BufRead>(mut reader: R) -> Result<Vec<IoBlock>, Error> {
     let mut v_ioblocks: Vec<IoBlock> = Vec::new();
-    let mut file = BufReader::with_capacity(2048, file);
 
+    let mut lineno = 0;
     let mut line = String::with_capacity(256);
-    while file.read_line(&mut line)? != 0 {
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
         let mut fields = line.split_whitespace();
+        let parse = |value: &str| {
+            value
+                .parse::<u64>()
+                .map_err(|_| Error::parse("/proc/diskstats", lineno))
+        };
+        let parse_id = |value: &str| {
+            value
+                .parse::<u32>()
+                .map_err(|_| Error::parse("/proc/diskstats", lineno))
+        };
 
-        let name = nth!(fields, 2)?;
-        // Based on the sysstat code:
-        // https://github.com/sysstat/sysstat/blob/1c711c1fd03ac638cfc1b25cdf700625c173fd2c/common.c#L200
-        // Some devices may have a slash in their name (eg. cciss/c0d0...) so replace them with `!`
-        if physical && !Path::new(&format!("/sys/block/{}/device", name.replace("/", "!"))).exists()
-        {
-            line.clear();
-            continue;
-        }
-        let read_count = nth!(fields, 0)?;
-        let read_bytes = nth!(fields, 1)?;
-        let write_count = nth!(fields, 0)?;
-        let write_bytes = nth!(fields, 1)?;
-        // Seconds
-        let busy_time = nth!(fields, 2)?;
+        // See https://www.kernel.org/doc/Documentation/ABI/testing/procfs-diskstats
+        let major = parse_id(nth!(fields, 0, "major")?)?;
+        let minor = parse_id(nth!(fields, 0, "minor")?)?;
+        let name = nth!(fields, 0, "device_name")?;
+        let read_count = parse(nth!(fields, 0, "read_count")?)?;
+        let read_merged = parse(nth!(fields, 0, "read_merged")?)?;
+        let read_bytes = parse(nth!(fields, 0, "read_sectors")?)? * DISK_SECTOR_SIZE;
+        // Milliseconds
+        let read_time = parse(nth!(fields, 0, "read_time")?)?;
+        let write_count = parse(nth!(fields, 0, "write_count")?)?;
+        let write_merged = parse(nth!(fields, 0, "write_merged")?)?;
+        let write_bytes = parse(nth!(fields, 0, "write_sectors")?)? * DISK_SECTOR_SIZE;
+        let write_time = parse(nth!(fields, 0, "write_time")?)?;
+        let in_flight = parse(nth!(fields, 0, "in_flight")?)?;
+        let busy_time = parse(nth!(fields, 0, "busy_time")?)?;
+        let weighted_time = parse(nth!(fields, 0, "weighted_time")?)?;
+        // The discard fields were added in 4.18 and the flush ones in 5.5
+        let mut next = || fields.next().map(parse).transpose();
+        let discard_count = next()?;
+        let discard_merged = next()?;
+        let discard_bytes = next()?.map(|sectors| sectors * DISK_SECTOR_SIZE);
+        let discard_time = next()?;
+        let flush_count = next()?;
+        let flush_time = next()?;
 
-        if fields.count() < 2 {
-            return Err(Error::new(ErrorKind::Other, "Invalid /proc/diskstats"));
-        }
         v_ioblocks.push(IoBlock {
+            major,
+            minor,
             device_name: name.to_owned(),
-            read_count: read_count.parse().unwrap(),
-            read_bytes: read_bytes.parse::<u64>().unwrap() * DISK_SECTOR_SIZE,
-            write_count: write_count.parse().unwrap(),
-            write_bytes: write_bytes.parse::<u64>().unwrap() * DISK_SECTOR_SIZE,
-            busy_time: busy_time.parse().unwrap(),
+            read_count,
+            read_merged,
+            read_bytes,
+            read_time,
+            write_count,
+            write_merged,
+            write_bytes,
+            write_time,
+            in_flight,
+            busy_time,
+            weighted_time,
+            discard_count,
+            discard_merged,
+            discard_bytes,
+            discard_time,
+            flush_count,
+            flush_time,
         });
         line.clear();
     }
@@ -57,16 +85,55 @@ fn _get_ioblocks(physical: bool) -> Result<Vec<IoBlock>, Error> {
     Ok(v_ioblocks)
 }
 
+#[inline]
+fn _get_ioblocks(root: &SysRoot, physical: bool) -> Result<Vec<IoBlock>, Error> {
+    let file = File::open(root.proc_path("diskstats"))?;
+    let mut v_ioblocks = parse_ioblocks(BufReader::with_capacity(2048, file))?;
+
+    if physical {
+        // Based on the sysstat code:
+        // https://github.com/sysstat/sysstat/blob/1c711c1fd03ac638cfc1b25cdf700625c173fd2c/common.c#L200
+        // Some devices may have a slash in their name (eg. cciss/c0d0...) so replace them with `!`
+        v_ioblocks.retain(|ioblock| {
+            root.sys_path(format!(
+                "block/{}/device",
+                ioblock.device_name.replace('/', "!")
+            ))
+            .exists()
+        });
+    }
+
+    Ok(v_ioblocks)
+}
+
 /// Get basic [IoBlock] (physical and virtual) info for each disks/partitions.
 ///
 /// [IoBlock]: ../disks/struct.IoBlock.html
 pub fn get_ioblocks() -> Result<Vec<IoBlock>, Error> {
-    _get_ioblocks(false)
+    _get_ioblocks(&DEFAULT_ROOT, false)
+}
+
+/// Get basic [IoBlock] (physical and virtual) info for each disks/partitions
+/// reading from the given [SysRoot].
+///
+/// [IoBlock]: ../disks/struct.IoBlock.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_ioblocks_with_root(root: &SysRoot) -> Result<Vec<IoBlock>, Error> {
+    _get_ioblocks(root, false)
 }
 
 /// Get basic [IoBlock] (physical) info for each physical disks.
 ///
 /// [IoBlock]: ../struct.IoBlock.html
 pub fn get_physical_ioblocks() -> Result<Vec<IoBlock>, Error> {
-    _get_ioblocks(true)
+    _get_ioblocks(&DEFAULT_ROOT, true)
+}
+
+/// Get basic [IoBlock] (physical) info for each physical disks
+/// reading from the given [SysRoot].
+///
+/// [IoBlock]: ../struct.IoBlock.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_physical_ioblocks_with_root(root: &SysRoot) -> Result<Vec<IoBlock>, Error> {
+    _get_ioblocks(root, true)
 }
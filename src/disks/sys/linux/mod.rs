@@ -1,5 +1,11 @@
+mod block_devices;
+mod ioblock_sampler;
 mod ioblocks;
+mod mountinfo;
 mod partitions;
 
+pub use block_devices::*;
+pub use ioblock_sampler::*;
 pub use ioblocks::*;
+pub use mountinfo::*;
 pub use partitions::*;
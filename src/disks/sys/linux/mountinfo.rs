@@ -0,0 +1,103 @@
+use crate::disks::MountInfo;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+/// Replace the octal escapes (\040 for a space, ...) of a mounts/mountinfo field.
+pub(crate) fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 4 <= bytes.len() {
+            let octal = std::str::from_utf8(&bytes[i + 1..i + 4]).unwrap_or("");
+            if let Ok(byte) = u8::from_str_radix(octal, 8) {
+                unescaped.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        unescaped.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+/// Parse the [MountInfo] entries from the content of /proc/[pid]/mountinfo.
+///
+/// See `man 5 proc` for the meaning of each fields.
+///
+/// [MountInfo]: ../disks/struct.MountInfo.html
+pub fn parse_mountinfo<R: BufRead>(mut reader: R) -> Result<Vec<MountInfo>, Error> {
+    let mut v_mounts: Vec<MountInfo> = Vec::new();
+    let mut lineno = 0;
+    let mut line = String::with_capacity(256);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let err = || Error::parse("/proc/self/mountinfo", lineno);
+        // 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+        // The optional fields are terminated by a single hyphen
+        let mut parts = line.splitn(2, " - ");
+        let (mut fields, mut fs_fields) = match (parts.next(), parts.next()) {
+            (Some(fields), Some(fs_fields)) => {
+                (fields.split_whitespace(), fs_fields.split_whitespace())
+            }
+            _ => return Err(err()),
+        };
+        let split = |options: &str| -> Vec<String> {
+            options.split(',').map(|option| option.to_owned()).collect()
+        };
+
+        let mount_id = nth!(fields, 0, "mount_id")?;
+        let parent_id = nth!(fields, 0, "parent_id")?;
+        let mut dev = nth!(fields, 0, "major:minor")?.splitn(2, ':');
+        let major = dev.next().and_then(|major| major.parse::<u32>().ok());
+        let minor = dev.next().and_then(|minor| minor.parse::<u32>().ok());
+        let root = nth!(fields, 0, "root")?;
+        let mount_point = nth!(fields, 0, "mount_point")?;
+        let mount_options = nth!(fields, 0, "mount_options")?;
+
+        v_mounts.push(MountInfo {
+            mount_id: mount_id.parse::<u32>().map_err(|_| err())?,
+            parent_id: parent_id.parse::<u32>().map_err(|_| err())?,
+            major: major.ok_or_else(err)?,
+            minor: minor.ok_or_else(err)?,
+            root: unescape(root),
+            mount_point: unescape(mount_point),
+            mount_options: split(mount_options),
+            propagation: fields.map(|field| field.to_owned()).collect(),
+            fs_type: nth!(fs_fields, 0, "fs_type")?.to_owned(),
+            source: unescape(nth!(fs_fields, 0, "source")?),
+            super_options: split(nth!(fs_fields, 0, "super_options")?),
+        });
+        line.clear();
+    }
+
+    Ok(v_mounts)
+}
+
+/// Read the [MountInfo] entries of the current process from /proc/self/mountinfo.
+pub(crate) fn read_mountinfo(root: &SysRoot) -> Result<Vec<MountInfo>, Error> {
+    let file = File::open(root.proc_path("self/mountinfo"))?;
+    parse_mountinfo(BufReader::with_capacity(4096, file))
+}
+
+/// Get the [MountInfo] entries of the mount namespace of the current process.
+///
+/// [MountInfo]: ../disks/struct.MountInfo.html
+pub fn get_mountinfo() -> Result<Vec<MountInfo>, Error> {
+    get_mountinfo_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the [MountInfo] entries of the mount namespace of the current process
+/// reading from the given [SysRoot].
+///
+/// [MountInfo]: ../disks/struct.MountInfo.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_mountinfo_with_root(root: &SysRoot) -> Result<Vec<MountInfo>, Error> {
+    read_mountinfo(root)
+}
@@ -1,50 +1,136 @@
-use crate::disks::{disk_usage, is_physical_filesys, Disks};
+use super::mountinfo::{read_mountinfo, unescape};
+use crate::disks::{get_disk_usage, is_physical_filesys, Disks, Mount, MountInfo};
+use crate::{Error, SysRoot, DEFAULT_ROOT};
 
-use std::io::Error;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::io::BufRead;
 
-#[inline]
-fn _get_partitions(physical: bool) -> Result<Vec<Disks>, Error> {
-    let file = File::open("/proc/mounts")?;
-    let mut vdisks: Vec<Disks> = Vec::new();
-    let mut file = BufReader::with_capacity(6144, file);
+/// Parse the [Mount] entries from the content of /proc/mounts.
+///
+/// The escaped characters (eg: `\040` for a space) of the paths are decoded.
+///
+/// [Mount]: ../disks/struct.Mount.html
+pub fn parse_mounts<R: BufRead>(mut reader: R) -> Result<Vec<Mount>, Error> {
+    let mut vmounts: Vec<Mount> = Vec::new();
 
     let mut line = String::with_capacity(512);
-    while file.read_line(&mut line)? != 0 {
+    while reader.read_line(&mut line)? != 0 {
         let mut fields = line.split_whitespace();
-        let name = fields.next().unwrap();
-        let path = fields.next().unwrap();
-        let filesys = fields.next().unwrap();
-        if physical && !is_physical_filesys(filesys) {
-            line.clear();
+        let name = nth!(fields, 0, "device")?;
+        let path = nth!(fields, 0, "mount_point")?;
+        let filesys = nth!(fields, 0, "fs_type")?;
+        let options = nth!(fields, 0, "options")?;
+        vmounts.push(Mount {
+            name: unescape(name),
+            mount_point: unescape(path),
+            fs_type: filesys.to_owned(),
+            options: options.split(',').map(|o| o.to_owned()).collect(),
+        });
+        line.clear()
+    }
+
+    Ok(vmounts)
+}
+
+/// Merge the per-mount and per-superblock options like /proc/mounts does.
+fn merge_options(mount: &MountInfo) -> Vec<String> {
+    let mut options = mount.mount_options.clone();
+    for option in &mount.super_options {
+        // The rw/ro of the superblock is already reflected by the mount one
+        if option != "rw" && option != "ro" && !options.contains(option) {
+            options.push(option.to_owned());
+        }
+    }
+
+    options
+}
+
+/// Return true if `inner` is a bind mount of a directory of `outer` (or of
+/// `outer` itself). Btrfs subvolumes share the same device but have different
+/// roots, so they're not bind mounts of each other.
+fn is_bind_of(inner: &MountInfo, outer: &MountInfo) -> bool {
+    if (inner.major, inner.minor) != (outer.major, outer.minor) {
+        return false;
+    }
+
+    match inner.root.strip_prefix(outer.root.as_str()) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || outer.root.ends_with('/'),
+        None => false,
+    }
+}
+
+#[inline]
+fn _get_partitions(root: &SysRoot, physical: bool) -> Result<Vec<Disks>, Error> {
+    let mut mounts: Vec<MountInfo> = Vec::new();
+    for mount in read_mountinfo(root)? {
+        if physical && !is_physical_filesys(&mount.fs_type) {
             continue;
         }
-        let usage: (u64, u64) = disk_usage(&path.as_bytes())?;
+        if mounts.iter().any(|other| is_bind_of(&mount, other)) {
+            continue;
+        }
+        // Replace the bind mounts of this one listed before it
+        let idx = mounts.iter().position(|other| is_bind_of(other, &mount));
+        mounts.retain(|other| !is_bind_of(other, &mount));
+        mounts.insert(idx.unwrap_or(mounts.len()), mount);
+    }
+
+    let mut vdisks: Vec<Disks> = Vec::with_capacity(mounts.len());
+    for mount in mounts {
+        let usage = get_disk_usage(mount.mount_point.as_bytes())?;
+        let options = merge_options(&mount);
         vdisks.push(Disks {
-            name: name.to_owned(),
-            mount_point: path.to_owned(),
-            total_space: usage.0 / (1024 * 1024),
-            avail_space: usage.1 / (1024 * 1024),
+            name: mount.source,
+            mount_point: mount.mount_point,
+            read_only: usage.read_only || options.iter().any(|option| option == "ro"),
+            fs_type: mount.fs_type,
+            options,
+            total_space: usage.total / (1024 * 1024),
+            avail_space: usage.avail / (1024 * 1024),
+            used_space: usage.used / (1024 * 1024),
+            inodes_total: usage.inodes_total,
+            inodes_free: usage.inodes_free,
+            inodes_used: usage.inodes_used,
         });
-        line.clear()
     }
 
     Ok(vdisks)
 }
 
-/// Return a Vec of [Disks] (physical and virtual) with their minimal information.
+/// Return a Vec of [Disks] (physical and virtual) with their mount and usage information.
+///
+/// The mounts are read from /proc/self/mountinfo, bind mounts of a directory
+/// of an already listed mount are skipped.
 ///
 /// [Disks]: ../disks/struct.Disks.html
 pub fn get_partitions() -> Result<Vec<Disks>, Error> {
-    _get_partitions(false)
+    _get_partitions(&DEFAULT_ROOT, false)
+}
+
+/// Return a Vec of [Disks] (physical and virtual) with their mount and usage information
+/// reading the mounts from the given [SysRoot].
+///
+/// Note that the usage is still queried using the mount points as seen by the host.
+///
+/// [Disks]: ../disks/struct.Disks.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_partitions_with_root(root: &SysRoot) -> Result<Vec<Disks>, Error> {
+    _get_partitions(root, false)
 }
 
-/// Return a Vec of [Disks] (physical) with their minimal information.
+/// Return a Vec of [Disks] (physical) with their mount and usage information.
 ///
 /// [Disks]: ../disks/struct.Disks.html
 pub fn get_partitions_physical() -> Result<Vec<Disks>, Error> {
-    _get_partitions(true)
+    _get_partitions(&DEFAULT_ROOT, true)
+}
+
+/// Return a Vec of [Disks] (physical) with their mount and usage information
+/// reading the mounts from the given [SysRoot].
+///
+/// Note that the usage is still queried using the mount points as seen by the host.
+///
+/// [Disks]: ../disks/struct.Disks.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_partitions_physical_with_root(root: &SysRoot) -> Result<Vec<Disks>, Error> {
+    _get_partitions(root, true)
 }
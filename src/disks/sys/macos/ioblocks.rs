@@ -1,5 +1,6 @@
 use crate::disks::IoBlock;
 use crate::utils::KeyVal;
+use crate::Error;
 
 use core_foundation_sys::{
     base::{kCFAllocatorDefault, CFRelease},
@@ -12,7 +13,6 @@ use io_kit_sys::{
     IOServiceMatching, *,
 };
 use libc::{c_char, c_void};
-use std::io::Error;
 
 /// Clear the pointers for dict and release disk objects
 unsafe fn release_c_ptr_ioblocks(
@@ -127,17 +127,35 @@ unsafe fn _get_ioblocks(physical: bool) -> Result<Vec<IoBlock>, Error> {
             let read_bytes = stats_dict.get_i64("Bytes (Read)\0")? as u64;
             let write_count = stats_dict.get_i64("Operations (Write)\0")? as u64;
             let write_bytes = stats_dict.get_i64("Bytes (Write)\0")? as u64;
-            let busy_time = (stats_dict.get_i64("Total Time (Read)\0")?
-                + stats_dict.get_i64("Total Time (Write)\0")?) as u64;
+            // Nanoseconds, converted to milliseconds
+            let read_time = stats_dict.get_i64("Total Time (Read)\0")? as u64 / 1_000_000;
+            let write_time = stats_dict.get_i64("Total Time (Write)\0")? as u64 / 1_000_000;
+            let busy_time = read_time + write_time;
             let device_name = parent_dict.get_string("BSD Name\0")?;
+            let major = parent_dict.get_i64("BSD Major\0")? as u32;
+            let minor = parent_dict.get_i64("BSD Minor\0")? as u32;
 
             Ok(IoBlock {
+                major,
+                minor,
                 device_name,
                 read_count,
+                read_merged: 0,
                 read_bytes,
+                read_time,
                 write_count,
+                write_merged: 0,
                 write_bytes,
+                write_time,
+                in_flight: 0,
                 busy_time,
+                weighted_time: 0,
+                discard_count: None,
+                discard_merged: None,
+                discard_bytes: None,
+                discard_time: None,
+                flush_count: None,
+                flush_time: None,
             })
         };
 
@@ -1,9 +1,9 @@
 use crate::binding::getfsstat64;
-use crate::disks::{disk_usage, is_physical_filesys, Disks};
+use crate::disks::{get_disk_usage, is_physical_filesys, Disks};
 use crate::to_str;
+use crate::Error;
 
 use libc::statfs;
-use std::io::Error;
 
 #[inline]
 fn _get_partitions(physical: bool) -> Result<Vec<Disks>, Error> {
@@ -32,29 +32,33 @@ fn _get_partitions(physical: bool) -> Result<Vec<Disks>, Error> {
             continue;
         }
         let path = to_str(stat.f_mntonname.as_ptr());
-        let usage: (u64, u64) = match disk_usage(&path.as_bytes()) {
-            Ok(val) => val,
-            Err(x) => return Err(x),
-        };
+        let usage = get_disk_usage(&path.as_bytes())?;
         vdisks.push(Disks {
             name: to_str(stat.f_mntfromname.as_ptr()).to_owned(),
             mount_point: path.to_owned(),
-            total_space: usage.0 / (1024 * 1024),
-            avail_space: usage.1 / (1024 * 1024),
+            fs_type: to_str(stat.f_fstypename.as_ptr()).to_owned(),
+            options: Vec::new(),
+            read_only: usage.read_only,
+            total_space: usage.total / (1024 * 1024),
+            avail_space: usage.avail / (1024 * 1024),
+            used_space: usage.used / (1024 * 1024),
+            inodes_total: usage.inodes_total,
+            inodes_free: usage.inodes_free,
+            inodes_used: usage.inodes_used,
         });
     }
 
     Ok(vdisks)
 }
 
-/// Return a Vec of [Disks] (physical and virtual) with their minimal information.
+/// Return a Vec of [Disks] (physical and virtual) with their mount and usage information.
 ///
 /// [Disks]: ../disks/struct.Disks.html
 pub fn get_partitions() -> Result<Vec<Disks>, Error> {
     _get_partitions(false)
 }
 
-/// Return a Vec of [Disks] (physical) with their minimal information.
+/// Return a Vec of [Disks] (physical) with their mount and usage information.
 ///
 /// [Disks]: ../disks/struct.Disks.html
 pub fn get_partitions_physical() -> Result<Vec<Disks>, Error> {
@@ -0,0 +1,75 @@
+use std::fmt;
+
+/// Error type returned by the functions of this crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// An I/O error occurred while reading a file or calling the OS.
+    Io(std::io::Error),
+
+    /// The content of `file` at `line` (starting at 1) couldn't be parsed.
+    Parse { file: String, line: usize },
+
+    /// A field or an entry expected to be present is missing.
+    MissingField(&'static str),
+
+    /// The information is not available on this system (old kernel, missing feature, ...).
+    Unsupported(&'static str),
+
+    /// The permission to read `file` was denied (eg: the process of another user).
+    PermissionDenied(String),
+}
+
+impl Error {
+    /// Return an [Error::Io] built from the last OS error (errno).
+    pub(crate) fn last_os_error() -> Self {
+        Error::Io(std::io::Error::last_os_error())
+    }
+
+    /// Return an [Error::PermissionDenied] for `file` if `err` is a permission
+    /// error, or an [Error::Io] otherwise.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn from_io<F: AsRef<str>>(err: std::io::Error, file: F) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::PermissionDenied => {
+                Error::PermissionDenied(file.as_ref().to_owned())
+            }
+            _ => Error::Io(err),
+        }
+    }
+
+    /// Return an [Error::Parse] for the `line` of `file`.
+    pub(crate) fn parse<F: AsRef<str>>(file: F, line: usize) -> Self {
+        Error::Parse {
+            file: file.as_ref().to_owned(),
+            line,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Parse { file, line } => write!(f, "Couldn't parse {} at line {}", file, line),
+            Error::MissingField(field) => write!(f, "The field {} is missing", field),
+            Error::Unsupported(what) => write!(f, "{} is not supported on this system", what),
+            Error::PermissionDenied(file) => write!(f, "Permission denied to read {}", file),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
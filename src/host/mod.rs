@@ -15,3 +15,15 @@ pub struct HostInfo {
     pub hostname: String,
     pub uptime: u64,
 }
+
+/// Struct containing the system-wide file handles information.
+///
+/// `allocated` and `unused` come from /proc/sys/fs/file-nr and `max` from /proc/sys/fs/file-max.
+/// Note that `unused` is always 0 since Linux 2.6.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct FileHandles {
+    pub allocated: u64,
+    pub unused: u64,
+    pub max: u64,
+}
@@ -0,0 +1,36 @@
+use crate::host::FileHandles;
+use crate::{read_and_trim, Error, SysRoot, DEFAULT_ROOT};
+
+/// Get the system-wide [FileHandles] info.
+///
+/// [FileHandles]: ../host/struct.FileHandles.html
+pub fn get_file_handles() -> Result<FileHandles, Error> {
+    get_file_handles_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the system-wide [FileHandles] info reading from the given [SysRoot].
+///
+/// [FileHandles]: ../host/struct.FileHandles.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_file_handles_with_root(root: &SysRoot) -> Result<FileHandles, Error> {
+    // file-nr: allocated unused max
+    let file_nr = read_and_trim(root.proc_path("sys/fs/file-nr"))?;
+    let mut fields = file_nr.split_whitespace();
+    let parse = |value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|_| Error::parse("/proc/sys/fs/file-nr", 1))
+    };
+    let allocated = parse(nth!(fields, 0, "allocated")?)?;
+    let unused = parse(nth!(fields, 0, "unused")?)?;
+
+    let max = read_and_trim(root.proc_path("sys/fs/file-max"))?
+        .parse::<u64>()
+        .map_err(|_| Error::parse("/proc/sys/fs/file-max", 1))?;
+
+    Ok(FileHandles {
+        allocated,
+        unused,
+        max,
+    })
+}
@@ -1,8 +1,8 @@
 use crate::cpu;
 use crate::host::{self, HostInfo};
 use crate::to_str;
+use crate::Error;
 
-use std::io::{Error, ErrorKind};
 use std::time::Duration;
 
 /// Get some basic [HostInfo] of the host.
@@ -10,10 +10,7 @@ use std::time::Duration;
 /// [HostInfo]: ../host/struct.HostInfo.html
 pub fn get_host_info() -> Result<HostInfo, Error> {
     let x = host::get_uname()?;
-    let y = match host::sysinfo() {
-        Ok(val) => val,
-        Err(x) => return Err(Error::new(ErrorKind::Other, x)),
-    };
+    let y = host::sysinfo()?;
 
     Ok(HostInfo {
         loadavg: cpu::get_loadavg_from_sysinfo(&y),
@@ -1,8 +1,10 @@
+mod file_handles;
 mod host_info;
 mod sysinfo;
 mod users;
 mod uuid;
 
+pub use file_handles::*;
 pub use host_info::*;
 pub use users::*;
 pub use uuid::*;
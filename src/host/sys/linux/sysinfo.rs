@@ -1,4 +1,4 @@
-use std::io::Error;
+use crate::Error;
 
 /// Return the sysinfo information
 pub(crate) fn sysinfo() -> Result<libc::sysinfo, Error> {
@@ -1,9 +1,9 @@
-use crate::to_str;
+use crate::{to_str, Error, SysRoot, DEFAULT_ROOT};
 
 use libc::{c_char, c_void, read, utmpx};
 use std::{
     fs::File,
-    io::{BufRead, BufReader, Error},
+    io::{BufRead, BufReader},
     mem,
     os::unix::prelude::*,
 };
@@ -13,19 +13,26 @@ use std::{
 /// Will get them from `/var/run/utmp`. If the file does not exist, it will return and Error.
 /// Be careful that the Error is only on Linux (and only if /var/run/utmp is not found).
 pub fn get_logged_users() -> Result<Vec<String>, Error> {
-    let utmp_file = File::open("/var/run/utmp")?;
+    get_logged_users_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the currently logged users reading `run/utmp` from the var root of the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_logged_users_with_root(root: &SysRoot) -> Result<Vec<String>, Error> {
+    let utmp_file = File::open(root.var_path("run/utmp"))?;
     let mut users: Vec<String> = Vec::new();
-    let buffer = std::mem::MaybeUninit::<utmpx>::uninit().as_mut_ptr();
+    let mut buffer = std::mem::MaybeUninit::<utmpx>::uninit();
 
     while unsafe {
         read(
             utmp_file.as_raw_fd(),
-            buffer as *mut c_void,
+            buffer.as_mut_ptr() as *mut c_void,
             mem::size_of::<utmpx>(),
         )
-    } != 0
+    } == mem::size_of::<utmpx>() as isize
     {
-        let cbuffer = unsafe { &*(buffer as *mut utmpx) as &utmpx };
+        let cbuffer = unsafe { &*buffer.as_ptr() };
         let cuser = unsafe { &*(&cbuffer.ut_user as *const [c_char]) };
 
         if cuser[0] != 0 && cbuffer.ut_type == libc::USER_PROCESS {
@@ -50,7 +57,14 @@ const UID_MAX: u16 = 60000;
 /// Read them from /etc/passwd and extract 'real users' based on their ID
 /// following convention from /etc/login.defs (UID_MIN & UID_MAX).
 pub fn get_users() -> Result<Vec<String>, Error> {
-    let file = File::open("/etc/passwd")?;
+    get_users_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the list of real users reading `passwd` from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_users_with_root(root: &SysRoot) -> Result<Vec<String>, Error> {
+    let file = File::open(root.etc_path("passwd"))?;
     let mut users: Vec<String> = Vec::new();
     let mut file = BufReader::with_capacity(2048, file);
 
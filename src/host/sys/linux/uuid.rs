@@ -1,16 +1,21 @@
-use crate::read_and_trim;
-
-use std::io::Error;
+use crate::{read_and_trim, Error, SysRoot, DEFAULT_ROOT};
 
 /// Get the machine UUID of the host.
 ///
 /// Read it from /etc/machine-id or /var/lib/dbus/machine-id.
 pub fn get_uuid() -> Result<String, Error> {
-    match read_and_trim("/etc/machine-id") {
+    get_uuid_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the machine UUID of the host reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_uuid_with_root(root: &SysRoot) -> Result<String, Error> {
+    match read_and_trim(root.etc_path("machine-id")) {
         Ok(machine_id) => match machine_id.is_empty() {
             false => Ok(machine_id),
-            true => Ok(read_and_trim("/var/lib/dbus/machine-id")?),
+            true => Ok(read_and_trim(root.var_path("lib/dbus/machine-id"))?),
         },
-        Err(_) => Ok(read_and_trim("/var/lib/dbus/machine-id")?),
+        Err(_) => Ok(read_and_trim(root.var_path("lib/dbus/machine-id"))?),
     }
 }
@@ -1,9 +1,9 @@
 use crate::cpu;
 use crate::host::{self, HostInfo};
 use crate::to_str;
+use crate::Error;
 
 use libc::{c_void, sysctl, timeval};
-use std::io::Error;
 use std::time::Duration;
 
 /// Get some basic [HostInfo] of the host.
@@ -13,12 +13,12 @@ pub fn get_host_info() -> Result<HostInfo, Error> {
     let x = host::get_uname()?;
 
     Ok(HostInfo {
-        loadavg: cpu::get_loadavg().unwrap(),
+        loadavg: cpu::get_loadavg()?,
         system: to_str(x.sysname.as_ptr()).to_owned(),
         os_version: host::get_os_version_from_uname(&x),
         kernel_version: host::get_os_version_from_uname(&x),
         hostname: host::get_hostname_from_uname(&x),
-        uptime: get_uptime().unwrap().as_secs(),
+        uptime: get_uptime()?.as_secs(),
     })
 }
 
@@ -1,7 +1,7 @@
 use crate::to_str;
+use crate::Error;
 
 use libc::{getutxent, setutxent, utmpx};
-use std::io::Error;
 
 /// Get the currently logged users.
 ///
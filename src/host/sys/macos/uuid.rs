@@ -1,4 +1,4 @@
-use crate::to_str;
+use crate::{to_str, Error};
 
 use core_foundation_sys::{
     base::{kCFAllocatorDefault, CFRelease, CFTypeRef},
@@ -8,7 +8,6 @@ use io_kit_sys::*;
 use io_kit_sys::{kIOMasterPortDefault, keys::kIOPlatformUUIDKey, IOServiceMatching};
 use libc::c_char;
 use libc::c_void;
-use std::io::{Error, ErrorKind};
 
 /// Get the machine UUID of the host.
 ///
@@ -32,7 +31,7 @@ pub fn get_uuid() -> Result<String, Error> {
             if !uuid_ascfstring.is_null() {
                 uuid = uuid_ascfstring as CFStringRef;
             } else {
-                return Err(Error::new(ErrorKind::Other, "Cannot get uuid_ascfstring"));
+                return Err(Error::MissingField("IOPlatformUUID"));
             }
             IOObjectRelease(platform_expert);
         } else {
@@ -40,10 +39,12 @@ pub fn get_uuid() -> Result<String, Error> {
         }
 
         let mut buffer = [0i8; 37];
-        if CFStringGetCString(uuid, buffer.as_mut_ptr(), 37, 134217984) == 0 {
-            return Err(Error::new(ErrorKind::Other, "Cannot get the buffer filled"));
-        }
+        let converted = CFStringGetCString(uuid, buffer.as_mut_ptr(), 37, 134217984);
         CFRelease(uuid as *mut c_void);
+        // The value is present but couldn't be converted (too long, not ASCII)
+        if converted == 0 {
+            return Err(Error::parse("IOPlatformUUID", 1));
+        }
 
         Ok(to_str(buffer.as_ptr()).to_owned())
     }
@@ -1,8 +1,8 @@
 use crate::host;
 use crate::to_str;
+use crate::Error;
 
 use libc::utsname;
-use std::io::Error;
 
 /// Get the `hostname` of the host.
 pub fn get_hostname() -> Result<String, Error> {
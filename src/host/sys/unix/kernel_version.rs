@@ -1,8 +1,8 @@
 use crate::host;
 use crate::to_str;
+use crate::Error;
 
 use libc::utsname;
-use std::io::Error;
 
 /// Get the kernel version.
 pub fn get_kernel_version() -> Result<String, Error> {
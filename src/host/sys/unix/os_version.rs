@@ -1,8 +1,8 @@
 use crate::host;
 use crate::to_str;
+use crate::Error;
 
 use libc::utsname;
-use std::io::Error;
 
 /// Get the os version.
 pub fn get_os_version() -> Result<String, Error> {
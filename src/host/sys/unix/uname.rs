@@ -1,4 +1,4 @@
-use std::io::Error;
+use crate::Error;
 
 /// Return a utsname instance
 ///
@@ -2,11 +2,14 @@
 //!
 //! It provide information about:
 //!
+//!  * Cgroups
 //!  * CPU
 //!  * Disks
 //!  * Host
 //!  * Memory
 //!  * Network
+//!  * Pressure stall information
+//!  * Processes
 //!  * Virtualization
 //!
 //! ## Quick start
@@ -28,17 +31,16 @@
 //! ```
 
 macro_rules! nth {
-    ($a:expr, $b:expr) => {
+    ($a:expr, $b:expr, $name:expr) => {
         match $a.nth($b) {
             Some(val) => Ok(val),
-            None => Err(Error::new(
-                ErrorKind::Other,
-                "The fields asked for does not exists",
-            )),
+            None => Err(crate::Error::MissingField($name)),
         }
     };
 }
 
+/// Cgroups information
+pub mod cgroup;
 /// CPU information
 pub mod cpu;
 /// Disks information
@@ -49,9 +51,23 @@ pub mod host;
 pub mod memory;
 /// Network information
 pub mod network;
+/// Pressure stall information
+pub mod pressure;
+/// Processes information
+pub mod process;
 /// Virtualization information
 pub mod virt;
 
+mod error;
+pub use error::Error;
+
+#[cfg(target_os = "linux")]
+mod sysroot;
+#[cfg(target_os = "linux")]
+pub use sysroot::SysRoot;
+#[cfg(target_os = "linux")]
+pub(crate) use sysroot::DEFAULT_ROOT;
+
 #[cfg(target_os = "macos")]
 pub mod macos_binding;
 #[cfg(target_os = "macos")]
@@ -65,9 +81,8 @@ use libc::c_char;
 use std::ffi::CStr;
 #[cfg(target_os = "linux")]
 use std::fs;
-use std::io::Error;
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
 lazy_static::lazy_static! {
     static ref PAGE_SIZE: u64 = {
         unsafe {
@@ -1,4 +1,4 @@
-use crate::to_str;
+use crate::{to_str, Error};
 
 use core_foundation_sys::{
     dictionary::{CFDictionaryGetValueIfPresent, CFDictionaryRef},
@@ -7,8 +7,6 @@ use core_foundation_sys::{
 };
 use io_kit_sys::*;
 use libc::c_void;
-use std::io::Error;
-use std::io::ErrorKind;
 
 pub trait KeyVal {
     unsafe fn get_dict(&self, key: &'static str) -> Result<CFDictionaryRef, Error>;
@@ -26,10 +24,7 @@ impl KeyVal for CFDictionaryRef {
             &mut stats_dict as *mut _ as *mut *const c_void,
         ) == 0
         {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("get_dict: {} not found in the dict", key),
-            ));
+            return Err(Error::MissingField(key.trim_end_matches('\0')));
         }
 
         Ok(stats_dict.assume_init())
@@ -44,10 +39,7 @@ impl KeyVal for CFDictionaryRef {
             _nbr.as_mut_ptr() as *mut *const c_void,
         ) == 0
         {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("get_i64: {} not found in the stats_dict", key),
-            ));
+            return Err(Error::MissingField(key.trim_end_matches('\0')));
         }
         let number = _nbr.assume_init();
         CFNumberGetValue(number, 4, &mut nbr as *mut _ as *mut c_void);
@@ -63,23 +55,15 @@ impl KeyVal for CFDictionaryRef {
             str_ref.as_mut_ptr() as *mut *const c_void,
         ) == 0
         {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("get_string: {} not found in the parent_dict", key),
-            ));
+            return Err(Error::MissingField(key.trim_end_matches('\0')));
         }
         let str_ref = str_ref.assume_init();
         // Max 64 char long
         let mut name = [0i8; 64];
         // I forgot why 134217984, sorry... (but seems to work)
         if CFStringGetCString(str_ref, name.as_mut_ptr(), 64, 134217984) == 0 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!(
-                    "Cannot get the buffer filled to transform the key({}) to String",
-                    key
-                ),
-            ));
+            // The value is present but couldn't be converted (too long, not ASCII)
+            return Err(Error::parse(key.trim_end_matches('\0'), 1));
         }
 
         Ok(to_str(name.as_ptr()).to_owned())
@@ -93,10 +77,7 @@ impl KeyVal for CFDictionaryRef {
             _ref.as_mut_ptr() as *mut *const c_void,
         ) == 0
         {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("get_bool: {} not found in the parent_dict", key),
-            ));
+            return Err(Error::MissingField(key.trim_end_matches('\0')));
         }
 
         Ok(CFBooleanGetValue(_ref.assume_init()))
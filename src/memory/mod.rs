@@ -6,24 +6,42 @@ use serde::Serialize;
 /// Struct containing the memory virtual information.
 ///
 /// All values are in MB.
+///
+/// `available` is an estimate of the memory available for starting new applications
+/// without swapping. On Linux `used` is computed as `total - available` (like free(1)).
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct Memory {
     pub total: u64,
     pub free: u64,
+    pub available: u64,
     pub used: u64,
     pub shared: u64,
     pub buffers: u64,
     pub cached: u64,
 }
 
+/// Source of the [EffectiveMemory] information.
+///
+/// [EffectiveMemory]: ../memory/struct.EffectiveMemory.html
 #[cfg(target_os = "linux")]
-impl Memory {
-    /// Return the Memory struct with used defined based on other values.
-    pub(crate) fn set_used(mut self) -> Self {
-        self.used = self.total - (self.free + self.buffers + self.cached);
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum MemorySource {
+    /// The memory limit and usage of the cgroup (v1 or v2)
+    Cgroup,
+    /// The memory of the host from /proc/meminfo
+    MemInfo,
+}
 
-        self
-    }
+/// Struct containing the memory the current process can effectively use.
+///
+/// All values are in MB.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveMemory {
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    pub source: MemorySource,
 }
 
 /// Struct containing the memory swap information.
@@ -35,3 +53,67 @@ pub struct Swap {
     pub free: u64,
     pub used: u64,
 }
+
+/// Struct containing the detailed memory information of /proc/meminfo.
+///
+/// All values are in bytes except for the `huge_pages_*` counters which are a number of pages.
+/// Fields which depends on the kernel version or configuration are Options.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct MemInfo {
+    pub mem_total: u64,
+    pub mem_free: u64,
+    /// Added in Linux 3.14
+    pub mem_available: Option<u64>,
+    pub buffers: u64,
+    pub cached: u64,
+    pub swap_cached: u64,
+    pub active: u64,
+    pub inactive: u64,
+    pub active_anon: u64,
+    pub inactive_anon: u64,
+    pub active_file: u64,
+    pub inactive_file: u64,
+    pub unevictable: u64,
+    pub mlocked: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    pub anon_pages: u64,
+    pub mapped: u64,
+    pub shmem: u64,
+    /// Added in Linux 4.20
+    pub kreclaimable: Option<u64>,
+    pub slab: u64,
+    pub sreclaimable: u64,
+    pub sunreclaim: u64,
+    pub kernel_stack: u64,
+    pub page_tables: u64,
+    pub nfs_unstable: u64,
+    pub bounce: u64,
+    pub writeback_tmp: u64,
+    pub commit_limit: u64,
+    pub committed_as: u64,
+    pub vmalloc_total: u64,
+    pub vmalloc_used: u64,
+    pub vmalloc_chunk: u64,
+    pub percpu: Option<u64>,
+    pub hardware_corrupted: Option<u64>,
+    pub anon_huge_pages: Option<u64>,
+    pub shmem_huge_pages: Option<u64>,
+    pub shmem_pmd_mapped: Option<u64>,
+    pub file_huge_pages: Option<u64>,
+    pub file_pmd_mapped: Option<u64>,
+    pub cma_total: Option<u64>,
+    pub cma_free: Option<u64>,
+    pub huge_pages_total: Option<u64>,
+    pub huge_pages_free: Option<u64>,
+    pub huge_pages_rsvd: Option<u64>,
+    pub huge_pages_surp: Option<u64>,
+    pub hugepagesize: Option<u64>,
+    pub hugetlb: Option<u64>,
+    pub direct_map_4k: Option<u64>,
+    pub direct_map_2m: Option<u64>,
+    pub direct_map_1g: Option<u64>,
+}
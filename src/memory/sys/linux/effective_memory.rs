@@ -0,0 +1,52 @@
+use crate::cgroup::{get_cgroup_memory_limit_with_root, read_current_memory};
+use crate::memory::{get_memory_with_root, EffectiveMemory, MemorySource};
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+const MB: u64 = 1024 * 1024;
+
+/// Return the [EffectiveMemory] the current process can use.
+///
+/// When the cgroup of the process has a memory limit lower than the host's memory,
+/// `total` is that limit and `used` is the working set of the cgroup (its usage minus the
+/// inactive file cache, like the kubelet). Otherwise the values of [get_memory] are used.
+///
+/// [EffectiveMemory]: ../memory/struct.EffectiveMemory.html
+/// [get_memory]: ../memory/fn.get_memory.html
+pub fn get_effective_memory() -> Result<EffectiveMemory, Error> {
+    get_effective_memory_with_root(&DEFAULT_ROOT)
+}
+
+/// Return the [EffectiveMemory] the current process can use reading from the given [SysRoot].
+///
+/// [EffectiveMemory]: ../memory/struct.EffectiveMemory.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_effective_memory_with_root(root: &SysRoot) -> Result<EffectiveMemory, Error> {
+    let memory = get_memory_with_root(root)?;
+
+    // Not being in a cgroup (or not being able to read it) means no limit
+    let limit = get_cgroup_memory_limit_with_root(root).ok().flatten();
+    if let Some(limit) = limit.filter(|limit| limit / MB < memory.total) {
+        if let Ok(Some(cgroup)) = read_current_memory(root) {
+            let total = limit / MB;
+            let used = cgroup
+                .current
+                .saturating_sub(cgroup.stat.inactive_file)
+                .min(limit)
+                / MB;
+
+            return Ok(EffectiveMemory {
+                total,
+                used,
+                available: total - used,
+                source: MemorySource::Cgroup,
+            });
+        }
+    }
+
+    Ok(EffectiveMemory {
+        total: memory.total,
+        used: memory.used,
+        available: memory.available,
+        source: MemorySource::MemInfo,
+    })
+}
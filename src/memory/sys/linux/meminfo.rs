@@ -0,0 +1,125 @@
+use crate::memory::MemInfo;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+/// Parse the [MemInfo] struct from the content of /proc/meminfo.
+///
+/// [MemInfo]: ../memory/struct.MemInfo.html
+pub fn parse_meminfo<R: BufRead>(mut reader: R) -> Result<MemInfo, Error> {
+    let mut meminfo = MemInfo::default();
+    let mut has_total = false;
+    let mut lineno = 0;
+    let mut line = String::with_capacity(64);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        // Lines are in the form of `Key:    value [kB]`
+        let mut parts = line.splitn(2, ':');
+        let (key, mut values) = match (parts.next(), parts.next()) {
+            (Some(key), Some(rest)) => (key, rest.split_whitespace()),
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+        let value = match values.next() {
+            Some(value) => value
+                .parse::<u64>()
+                .map_err(|_| Error::parse("/proc/meminfo", lineno))?,
+            None => {
+                line.clear();
+                continue;
+            }
+        };
+        // Values are in kB, except for the HugePages_ counters
+        let value = match values.next() {
+            Some("kB") => value * 1024,
+            _ => value,
+        };
+
+        match key {
+            "MemTotal" => meminfo.mem_total = value,
+            "MemFree" => meminfo.mem_free = value,
+            "MemAvailable" => meminfo.mem_available = Some(value),
+            "Buffers" => meminfo.buffers = value,
+            "Cached" => meminfo.cached = value,
+            "SwapCached" => meminfo.swap_cached = value,
+            "Active" => meminfo.active = value,
+            "Inactive" => meminfo.inactive = value,
+            "Active(anon)" => meminfo.active_anon = value,
+            "Inactive(anon)" => meminfo.inactive_anon = value,
+            "Active(file)" => meminfo.active_file = value,
+            "Inactive(file)" => meminfo.inactive_file = value,
+            "Unevictable" => meminfo.unevictable = value,
+            "Mlocked" => meminfo.mlocked = value,
+            "SwapTotal" => meminfo.swap_total = value,
+            "SwapFree" => meminfo.swap_free = value,
+            "Dirty" => meminfo.dirty = value,
+            "Writeback" => meminfo.writeback = value,
+            "AnonPages" => meminfo.anon_pages = value,
+            "Mapped" => meminfo.mapped = value,
+            "Shmem" => meminfo.shmem = value,
+            "KReclaimable" => meminfo.kreclaimable = Some(value),
+            "Slab" => meminfo.slab = value,
+            "SReclaimable" => meminfo.sreclaimable = value,
+            "SUnreclaim" => meminfo.sunreclaim = value,
+            "KernelStack" => meminfo.kernel_stack = value,
+            "PageTables" => meminfo.page_tables = value,
+            "NFS_Unstable" => meminfo.nfs_unstable = value,
+            "Bounce" => meminfo.bounce = value,
+            "WritebackTmp" => meminfo.writeback_tmp = value,
+            "CommitLimit" => meminfo.commit_limit = value,
+            "Committed_AS" => meminfo.committed_as = value,
+            "VmallocTotal" => meminfo.vmalloc_total = value,
+            "VmallocUsed" => meminfo.vmalloc_used = value,
+            "VmallocChunk" => meminfo.vmalloc_chunk = value,
+            "Percpu" => meminfo.percpu = Some(value),
+            "HardwareCorrupted" => meminfo.hardware_corrupted = Some(value),
+            "AnonHugePages" => meminfo.anon_huge_pages = Some(value),
+            "ShmemHugePages" => meminfo.shmem_huge_pages = Some(value),
+            "ShmemPmdMapped" => meminfo.shmem_pmd_mapped = Some(value),
+            "FileHugePages" => meminfo.file_huge_pages = Some(value),
+            "FilePmdMapped" => meminfo.file_pmd_mapped = Some(value),
+            "CmaTotal" => meminfo.cma_total = Some(value),
+            "CmaFree" => meminfo.cma_free = Some(value),
+            "HugePages_Total" => meminfo.huge_pages_total = Some(value),
+            "HugePages_Free" => meminfo.huge_pages_free = Some(value),
+            "HugePages_Rsvd" => meminfo.huge_pages_rsvd = Some(value),
+            "HugePages_Surp" => meminfo.huge_pages_surp = Some(value),
+            "Hugepagesize" => meminfo.hugepagesize = Some(value),
+            "Hugetlb" => meminfo.hugetlb = Some(value),
+            "DirectMap4k" => meminfo.direct_map_4k = Some(value),
+            "DirectMap2M" => meminfo.direct_map_2m = Some(value),
+            "DirectMap1G" => meminfo.direct_map_1g = Some(value),
+            _ => {}
+        }
+        has_total |= key == "MemTotal";
+
+        line.clear();
+    }
+
+    if !has_total {
+        return Err(Error::MissingField("MemTotal"));
+    }
+
+    Ok(meminfo)
+}
+
+/// Return the [MemInfo] struct with all the information exposed by /proc/meminfo.
+///
+/// [MemInfo]: ../memory/struct.MemInfo.html
+pub fn get_meminfo() -> Result<MemInfo, Error> {
+    get_meminfo_with_root(&DEFAULT_ROOT)
+}
+
+/// Return the [MemInfo] struct reading from the given [SysRoot].
+///
+/// [MemInfo]: ../memory/struct.MemInfo.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_meminfo_with_root(root: &SysRoot) -> Result<MemInfo, Error> {
+    let file = File::open(root.proc_path("meminfo"))?;
+    parse_meminfo(BufReader::with_capacity(4096, file))
+}
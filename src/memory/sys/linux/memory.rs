@@ -1,84 +1,108 @@
-use crate::memory::Memory;
+use crate::memory::{parse_meminfo, MemInfo, Memory};
+use crate::{Error, SysRoot, DEFAULT_ROOT, PAGE_SIZE};
 
 use std::{
     fs::File,
-    io::{BufRead, BufReader, Error, ErrorKind},
+    io::{BufRead, BufReader},
 };
 
-/// Return the [Memory] struct.
+const MB: u64 = 1024 * 1024;
+
+/// Estimate the available memory the same way the kernel does for MemAvailable.
 ///
-/// Note that `used` is computed from Total, Free, Buffers and Cached (which is Cached + SReclaimable).
+/// Used for kernels older than 3.14 which don't expose MemAvailable, see
+/// <https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/commit/?id=34e431b0ae398fc54ea69ff85ec700722c9da773>
 ///
-/// [Memory]: ../memory/struct.Memory.html
-pub fn get_memory() -> Result<Memory, Error> {
-    let file = File::open("/proc/meminfo")?;
-    let mut file = BufReader::with_capacity(2048, file);
+/// `wmark_low` is the sum of the low watermarks of all zones (in bytes).
+fn estimate_available(meminfo: &MemInfo, wmark_low: u64) -> u64 {
+    // Free memory minus the amount the kernel keeps for itself
+    let mut available = meminfo.mem_free.saturating_sub(wmark_low);
+    // Part of the page cache which can be dropped without trashing
+    let pagecache = meminfo.active_file + meminfo.inactive_file;
+    available += pagecache - std::cmp::min(pagecache / 2, wmark_low);
+    // Part of the slab which can be reclaimed
+    available += meminfo.sreclaimable - std::cmp::min(meminfo.sreclaimable / 2, wmark_low);
 
-    let mut matched_lines = 0u8;
-    let mut memory = Memory::default();
+    available
+}
+
+/// Parse the sum of the low watermarks (in bytes) of all zones from the content of /proc/zoneinfo.
+fn parse_wmark_low<R: BufRead>(mut reader: R) -> Result<u64, Error> {
+    let mut wmark_low = 0;
+    let mut lineno = 0;
     let mut line = String::with_capacity(64);
-    while file.read_line(&mut line)? != 0 {
-        // We only need 6 values which can be detected by their 4first bytes
-        let first_bytes = &line.as_bytes()[..4];
-        match first_bytes {
-            b"MemT" | b"MemF" | b"Buff" | b"Cach" | b"Shme" | b"SRec" => {}
-            _ => {
-                line.clear();
-                continue;
-            }
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let mut fields = line.split_whitespace();
+        if let (Some("low"), Some(pages)) = (fields.next(), fields.next()) {
+            wmark_low += pages
+                .parse::<u64>()
+                .map_err(|_| Error::parse("/proc/zoneinfo", lineno))?;
         }
+        line.clear();
+    }
 
-        // Split the line at the : separator
-        let mut parts = line.splitn(2, ':');
-        // Check if if the value we search is the splitted one
-        // if so, return a pointer to the memory zone we'll modify.
-        let field = match parts.next() {
-            Some("MemTotal") => &mut memory.total,
-            Some("MemFree") => &mut memory.free,
-            Some("Buffers") => &mut memory.buffers,
-            Some("Cached") => &mut memory.cached,
-            Some("SReclaimable") => &mut memory.cached,
-            Some("Shmem") => &mut memory.shared,
-            _ => {
-                line.clear();
-                continue;
-            }
-        };
+    Ok(wmark_low * *PAGE_SIZE)
+}
 
-        // Get the value part
-        match parts.next() {
-            Some(value) => {
-                // Increment the field we previously got (pointer)
-                *field += {
-                    // Trim to only get the bytes value
-                    let kbytes = match value.trim_start().splitn(2, ' ').next() {
-                        Some(kkbytes) => kkbytes.parse::<u64>().unwrap(),
-                        None => {
-                            line.clear();
-                            continue;
-                        }
-                    };
-                    matched_lines += 1;
-                    kbytes / 1024
-                }
-            }
+/// Build the [Memory] struct from the [MemInfo] one.
+fn memory_from_meminfo(meminfo: &MemInfo, wmark_low: u64) -> Memory {
+    let available = meminfo
+        .mem_available
+        .unwrap_or_else(|| estimate_available(meminfo, wmark_low))
+        .min(meminfo.mem_total);
 
-            None => {
-                line.clear();
-                continue;
-            }
-        }
+    Memory {
+        total: meminfo.mem_total / MB,
+        free: meminfo.mem_free / MB,
+        available: available / MB,
+        used: (meminfo.mem_total - available) / MB,
+        shared: meminfo.shmem / MB,
+        buffers: meminfo.buffers / MB,
+        cached: (meminfo.cached + meminfo.sreclaimable) / MB,
+    }
+}
 
-        // If we've found all our information, we can return.
-        if matched_lines == 6 {
-            return Ok(memory.set_used());
-        }
+/// Parse the [Memory] struct from the content of /proc/meminfo.
+///
+/// On kernels without MemAvailable (< 3.14), `available` is estimated
+/// without the zones watermarks, see [get_memory_with_root].
+///
+/// [Memory]: ../memory/struct.Memory.html
+/// [get_memory_with_root]: ../memory/fn.get_memory_with_root.html
+pub fn parse_memory<R: BufRead>(reader: R) -> Result<Memory, Error> {
+    let meminfo = parse_meminfo(reader)?;
 
-        line.clear();
-    }
+    Ok(memory_from_meminfo(&meminfo, 0))
+}
+
+/// Return the [Memory] struct.
+///
+/// Note that `available` is MemAvailable, or the kernel's estimate of it on kernels
+/// older than 3.14, and `used` is Total - Available (like free(1)).
+/// `cached` is Cached + SReclaimable.
+///
+/// [Memory]: ../memory/struct.Memory.html
+pub fn get_memory() -> Result<Memory, Error> {
+    get_memory_with_root(&DEFAULT_ROOT)
+}
+
+/// Return the [Memory] struct reading from the given [SysRoot].
+///
+/// [Memory]: ../memory/struct.Memory.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_memory_with_root(root: &SysRoot) -> Result<Memory, Error> {
+    let file = File::open(root.proc_path("meminfo"))?;
+    let meminfo = parse_meminfo(BufReader::with_capacity(4096, file))?;
+
+    // The watermarks are only needed to estimate the available memory
+    let wmark_low = match meminfo.mem_available {
+        Some(_) => 0,
+        None => match File::open(root.proc_path("zoneinfo")) {
+            Ok(file) => parse_wmark_low(BufReader::with_capacity(4096, file))?,
+            Err(_) => 0,
+        },
+    };
 
-    Err(Error::new(
-        ErrorKind::Other,
-        "Couldn't get the memory information",
-    ))
+    Ok(memory_from_meminfo(&meminfo, wmark_low))
 }
@@ -1,5 +1,9 @@
+mod effective_memory;
+mod meminfo;
 mod memory;
 mod swap;
 
+pub use effective_memory::*;
+pub use meminfo::*;
 pub use memory::*;
 pub use swap::*;
@@ -1,10 +1,11 @@
 use crate::host;
 use crate::memory::Swap;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
 
 use libc::c_ulong;
 use std::{
     fs::File,
-    io::{BufRead, BufReader, Error, ErrorKind},
+    io::{BufRead, BufReader},
 };
 
 /// Return the [Swap] struct.
@@ -13,10 +14,7 @@ use std::{
 #[allow(clippy::useless_conversion)]
 pub fn get_swap() -> Result<Swap, Error> {
     // Init sysinfo
-    let y = match host::sysinfo() {
-        Ok(val) => val,
-        Err(x) => return Err(Error::new(ErrorKind::Other, x)),
-    };
+    let y = host::sysinfo()?;
 
     // Compute the values from the sysinfo and divide by (1024 * 1024) for MB
     let total_swap: u64 = ((y.totalswap * y.mem_unit as c_ulong) / (1024 * 1024)).into();
@@ -33,7 +31,14 @@ pub fn get_swap() -> Result<Swap, Error> {
 ///
 /// Read /proc/swaps and count the number of lines, if more than 1 then swap is enabled.
 pub fn has_swap() -> Result<bool, Error> {
-    let file = File::open("/proc/swaps")?;
+    has_swap_with_root(&DEFAULT_ROOT)
+}
+
+/// Determine if the system uses Swap reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn has_swap_with_root(root: &SysRoot) -> Result<bool, Error> {
+    let file = File::open(root.proc_path("swaps"))?;
     let mut file = BufReader::with_capacity(512, file);
 
     let mut lines = 0u8;
@@ -1,17 +1,18 @@
 use crate::binding::{host_statistics64, mach_host_self, vm_statistics64};
 use crate::memory::Memory;
+use crate::Error;
 use crate::PAGE_SIZE;
 
 use mach::mach_port::mach_port_deallocate;
 use mach::traps::mach_task_self;
 use mach::vm_types::integer_t;
-use std::io::Error;
 
 /// Return the [Memory] struct.
 ///
 /// Will use unsafe syscall due to specific OSX implementation.
 ///
 /// Note that shared, buffers and cached are not used nor declared on MacOS, and so those value are zeroed.
+/// `available` is computed from the free and inactive pages.
 ///
 /// [Memory]: ../memory/struct.Memory.html
 pub fn get_memory() -> Result<Memory, Error> {
@@ -55,6 +56,8 @@ pub fn get_memory() -> Result<Memory, Error> {
         total: total / (1024 * 1024),
         free: (u64::from(vm_stats.free_count - vm_stats.speculative_count) * (*PAGE_SIZE))
             / (1024 * 1024),
+        available: (u64::from(vm_stats.free_count + vm_stats.inactive_count) * (*PAGE_SIZE))
+            / (1024 * 1024),
         used: (u64::from(vm_stats.active_count + vm_stats.wire_count) * (*PAGE_SIZE))
             / (1024 * 1024),
         shared: 0,
@@ -1,6 +1,5 @@
 use crate::memory::Swap;
-
-use std::io::Error;
+use crate::Error;
 
 /// Return the [Swap] struct.
 ///
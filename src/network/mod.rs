@@ -3,8 +3,12 @@ mod sys;
 pub use sys::*;
 
 use serde::Serialize;
+#[cfg(target_os = "linux")]
+use std::net::IpAddr;
 
 /// Struct containing the IO counters for the network interfaces.
+///
+/// The fifo, frame, compressed and carrier counters are always 0 on macOS.
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct IoNet {
     pub interface: String,
@@ -12,8 +16,158 @@ pub struct IoNet {
     pub rx_packets: u64,
     pub rx_errs: u64,
     pub rx_drop: u64,
+    pub rx_fifo: u64,
+    /// Framing errors
+    pub rx_frame: u64,
+    pub rx_compressed: u64,
+    pub rx_multicast: u64,
     pub tx_bytes: u64,
     pub tx_packets: u64,
     pub tx_errs: u64,
     pub tx_drop: u64,
+    pub tx_fifo: u64,
+    /// Collisions
+    pub tx_colls: u64,
+    /// Carrier losses
+    pub tx_carrier: u64,
+    pub tx_compressed: u64,
+}
+
+/// Struct containing the rates (per second) of the IO counters of a network interface
+/// between two [IoNet] snapshots.
+///
+/// [IoNet]: ../network/struct.IoNet.html
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct IoNetRate {
+    pub interface: String,
+    pub rx_bytes: f64,
+    pub rx_packets: f64,
+    pub rx_errs: f64,
+    pub rx_drop: f64,
+    pub rx_fifo: f64,
+    pub rx_frame: f64,
+    pub rx_compressed: f64,
+    pub rx_multicast: f64,
+    pub tx_bytes: f64,
+    pub tx_packets: f64,
+    pub tx_errs: f64,
+    pub tx_drop: f64,
+    pub tx_fifo: f64,
+    pub tx_colls: f64,
+    pub tx_carrier: f64,
+    pub tx_compressed: f64,
+}
+
+#[cfg(target_os = "linux")]
+impl IoNetRate {
+    /// Compute the [IoNetRate] between two snapshots of the same interface taken `elapsed` apart.
+    ///
+    /// Return None if any counter went backward (the counters were reset).
+    ///
+    /// [IoNetRate]: ../network/struct.IoNetRate.html
+    pub fn from_delta(
+        prev: &IoNet,
+        curr: &IoNet,
+        elapsed: std::time::Duration,
+    ) -> Option<IoNetRate> {
+        let secs = elapsed.as_secs_f64();
+        let rate = |prev: u64, curr: u64| -> Option<f64> {
+            let delta = curr.checked_sub(prev)?;
+            match secs > 0.0 {
+                true => Some(delta as f64 / secs),
+                false => Some(0.0),
+            }
+        };
+
+        Some(IoNetRate {
+            interface: curr.interface.clone(),
+            rx_bytes: rate(prev.rx_bytes, curr.rx_bytes)?,
+            rx_packets: rate(prev.rx_packets, curr.rx_packets)?,
+            rx_errs: rate(prev.rx_errs, curr.rx_errs)?,
+            rx_drop: rate(prev.rx_drop, curr.rx_drop)?,
+            rx_fifo: rate(prev.rx_fifo, curr.rx_fifo)?,
+            rx_frame: rate(prev.rx_frame, curr.rx_frame)?,
+            rx_compressed: rate(prev.rx_compressed, curr.rx_compressed)?,
+            rx_multicast: rate(prev.rx_multicast, curr.rx_multicast)?,
+            tx_bytes: rate(prev.tx_bytes, curr.tx_bytes)?,
+            tx_packets: rate(prev.tx_packets, curr.tx_packets)?,
+            tx_errs: rate(prev.tx_errs, curr.tx_errs)?,
+            tx_drop: rate(prev.tx_drop, curr.tx_drop)?,
+            tx_fifo: rate(prev.tx_fifo, curr.tx_fifo)?,
+            tx_colls: rate(prev.tx_colls, curr.tx_colls)?,
+            tx_carrier: rate(prev.tx_carrier, curr.tx_carrier)?,
+            tx_compressed: rate(prev.tx_compressed, curr.tx_compressed)?,
+        })
+    }
+}
+
+/// Struct containing the [IoNetRate] of each interface as returned by an [IoNetSampler].
+///
+/// The added interfaces and those whose counters were reset don't have any [IoNetRate]
+/// until the next sample. A renamed interface is listed as (old name, new name)
+/// and keeps its rates.
+///
+/// [IoNetRate]: ../network/struct.IoNetRate.html
+/// [IoNetSampler]: ../network/struct.IoNetSampler.html
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct IoNetSample {
+    pub ionets: Vec<IoNetRate>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub renamed: Vec<(String, String)>,
+    pub reset: Vec<String>,
+}
+
+/// Kind of a network interface, derived from its sysfs attributes (and its driver
+/// for the veth interfaces of the current network namespace).
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum InterfaceKind {
+    /// Wired interface backed by a device (including SR-IOV VFs, USB adapters and modems)
+    Physical,
+    Loopback,
+    Bridge,
+    Bond,
+    Vlan,
+    Veth,
+    Tun,
+    Tap,
+    Wireguard,
+    /// Wireless interface (802.11) backed by a device
+    Wireless,
+    /// Any other virtual interface (macvlan, ipvlan, vxlan, gretap, ...)
+    Other,
+}
+
+/// Struct containing an IPv4 or IPv6 address of a network interface.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub struct InterfaceAddr {
+    pub addr: IpAddr,
+    /// Length of the network prefix (eg: 24 for a 255.255.255.0 netmask)
+    pub prefix_len: u8,
+}
+
+/// Struct containing the description of a network interface (from /sys/class/net).
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Serialize)]
+pub struct Interface {
+    pub name: String,
+    pub index: u32,
+    /// None if the interface doesn't have any hardware address (eg: tun)
+    pub mac: Option<String>,
+    pub mtu: u32,
+    /// RFC 2863 operational state (eg: up, down, dormant or unknown)
+    pub operstate: String,
+    /// None if the interface is administratively down
+    pub carrier: Option<bool>,
+    /// full or half, None if unknown
+    pub duplex: Option<String>,
+    /// Link speed in Mb/s, None if unknown (link down or virtual interface)
+    pub speed: Option<u64>,
+    pub addresses: Vec<InterfaceAddr>,
+    pub kind: InterfaceKind,
+    pub is_virtual: bool,
 }
@@ -0,0 +1,352 @@
+use crate::network::{Interface, InterfaceAddr, InterfaceKind};
+use crate::{read_and_trim, Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    collections::HashMap,
+    ffi::{CStr, CString},
+    fs,
+    io::ErrorKind,
+    mem,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::Path,
+};
+
+/// Read the file `name` of the interface `dir`, None if it can't be read.
+///
+/// Some attributes (eg: carrier or speed) return EINVAL when the link is down.
+fn read_attr(dir: &Path, name: &str) -> Option<String> {
+    read_and_trim(dir.join(name)).ok()
+}
+
+/// Parse the numerical attribute `name` of the interface `dir`.
+fn parse_attr<T: std::str::FromStr>(dir: &Path, name: &str) -> Result<T, Error> {
+    read_and_trim(dir.join(name))?
+        .parse::<T>()
+        .map_err(|_| Error::parse(dir.join(name).to_string_lossy(), 1))
+}
+
+// ARPHRD_* values of the type attribute, see include/uapi/linux/if_arp.h
+const ARPHRD_ETHER: &str = "1";
+const ARPHRD_LOOPBACK: &str = "772";
+
+// Flags of the tun_flags attribute, see include/uapi/linux/if_tun.h
+const IFF_TAP: u32 = 0x0002;
+
+/// Return the DEVTYPE of the uevent attribute of the interface `dir`, if any.
+fn read_devtype(dir: &Path) -> Option<String> {
+    read_attr(dir, "uevent")?
+        .lines()
+        .find_map(|line| line.strip_prefix("DEVTYPE="))
+        .map(str::to_owned)
+}
+
+// ioctl querying the driver of an interface, see include/uapi/linux/sockios.h
+// and include/uapi/linux/ethtool.h
+const SIOCETHTOOL: libc::c_ulong = 0x8946;
+const ETHTOOL_GDRVINFO: u32 = 0x0000_0003;
+
+/// struct ethtool_drvinfo
+#[repr(C)]
+struct EthtoolDrvinfo {
+    cmd: u32,
+    driver: [libc::c_char; 32],
+    version: [libc::c_char; 32],
+    fw_version: [libc::c_char; 32],
+    bus_info: [libc::c_char; 32],
+    erom_version: [libc::c_char; 32],
+    reserved2: [libc::c_char; 12],
+    n_priv_flags: u32,
+    n_stats: u32,
+    testinfo_len: u32,
+    eedump_len: u32,
+    regdump_len: u32,
+}
+
+/// struct ifmap, the largest member of the ifreq union (its size depends on c_ulong).
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Ifmap {
+    mem_start: libc::c_ulong,
+    mem_end: libc::c_ulong,
+    base_addr: libc::c_ushort,
+    irq: libc::c_uchar,
+    dma: libc::c_uchar,
+    port: libc::c_uchar,
+}
+
+/// The union of struct ifreq, only ifru_data is used but the others give it its size.
+#[repr(C)]
+union IfreqIfru {
+    ifru_addr: libc::sockaddr,
+    ifru_map: Ifmap,
+    ifru_data: *mut libc::c_void,
+}
+
+/// struct ifreq, not available in the libc crate.
+#[repr(C)]
+struct Ifreq {
+    ifr_name: [libc::c_char; libc::IFNAMSIZ],
+    ifr_ifru: IfreqIfru,
+}
+
+/// Return the driver of the interface `name` using ethtool, None if `name` isn't
+/// the interface `index` of the current network namespace (eg: reading from a [SysRoot]).
+///
+/// [SysRoot]: ../struct.SysRoot.html
+fn ethtool_driver(name: &str, index: u32) -> Option<String> {
+    let cname = CString::new(name).ok()?;
+    if name.len() >= libc::IFNAMSIZ || unsafe { libc::if_nametoindex(cname.as_ptr()) } != index {
+        return None;
+    }
+
+    let mut drvinfo: EthtoolDrvinfo = unsafe { mem::zeroed() };
+    drvinfo.cmd = ETHTOOL_GDRVINFO;
+    let mut ifreq: Ifreq = unsafe { mem::zeroed() };
+    for (dst, src) in ifreq.ifr_name.iter_mut().zip(name.bytes()) {
+        *dst = src as libc::c_char;
+    }
+    ifreq.ifr_ifru.ifru_data = &mut drvinfo as *mut EthtoolDrvinfo as *mut libc::c_void;
+
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if fd == -1 {
+        return None;
+    }
+    let ret = unsafe { libc::ioctl(fd, SIOCETHTOOL as _, &mut ifreq) };
+    unsafe { libc::close(fd) };
+    if ret == -1 {
+        return None;
+    }
+
+    Some(
+        unsafe { CStr::from_ptr(drvinfo.driver.as_ptr()) }
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+/// Return true if the sysfs attributes of the interface `dir` match a veth: it's linked
+/// to its peer, which either links back to it or lives in another network namespace.
+///
+/// macvlan, ipvlan, macvtap, ... are linked to their lower device instead, which
+/// is listed as a lower_* adjacency when it's in the same network namespace.
+/// Tunnels not bound to a device (eg: gretap) have an iflink of 0.
+fn has_veth_peer(dir: &Path) -> bool {
+    let (ifindex, iflink) = match (read_attr(dir, "ifindex"), read_attr(dir, "iflink")) {
+        (Some(ifindex), Some(iflink)) if ifindex != iflink && iflink != "0" => (ifindex, iflink),
+        _ => return false,
+    };
+    let has_lower = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .any(|entry| entry.file_name().to_string_lossy().starts_with("lower_")),
+        Err(_) => return false,
+    };
+    if has_lower {
+        return false;
+    }
+
+    let interfaces = match dir.parent().map(fs::read_dir) {
+        Some(Ok(interfaces)) => interfaces,
+        _ => return false,
+    };
+    for interface in interfaces.flatten() {
+        let peer = interface.path();
+        if read_attr(&peer, "ifindex").as_deref() == Some(iflink.as_str()) {
+            return read_attr(&peer, "iflink").as_deref() == Some(ifindex.as_str());
+        }
+    }
+
+    true
+}
+
+/// Return true if the interface `dir` is one end of a veth pair.
+///
+/// The driver is asked to ethtool when the interface belongs to the current network
+/// namespace, otherwise only the sysfs attributes can be used.
+fn is_veth(dir: &Path) -> bool {
+    let name = dir.file_name().map(|name| name.to_string_lossy());
+    let index = read_attr(dir, "ifindex").and_then(|index| index.parse::<u32>().ok());
+    match (name, index) {
+        (Some(name), Some(index)) => match ethtool_driver(&name, index) {
+            Some(driver) => driver == "veth",
+            None => has_veth_peer(dir),
+        },
+        _ => false,
+    }
+}
+
+/// Detect the [InterfaceKind] of the interface located at `dir`.
+///
+/// [InterfaceKind]: ../network/enum.InterfaceKind.html
+fn detect_kind(dir: &Path) -> InterfaceKind {
+    let devtype = read_devtype(dir);
+    let arphrd = read_attr(dir, "type");
+
+    match devtype.as_deref() {
+        _ if arphrd.as_deref() == Some(ARPHRD_LOOPBACK) => InterfaceKind::Loopback,
+        Some("wlan") => InterfaceKind::Wireless,
+        _ if dir.join("wireless").exists() || dir.join("phy80211").exists() => {
+            InterfaceKind::Wireless
+        }
+        Some("bridge") => InterfaceKind::Bridge,
+        _ if dir.join("bridge").exists() => InterfaceKind::Bridge,
+        Some("bond") => InterfaceKind::Bond,
+        _ if dir.join("bonding").exists() => InterfaceKind::Bond,
+        Some("vlan") => InterfaceKind::Vlan,
+        Some("wireguard") => InterfaceKind::Wireguard,
+        // Backed by a device (PCI, USB, ...), even when it's a VF, a tether or a modem
+        _ if dir.join("device").exists() => InterfaceKind::Physical,
+        Some(_) => InterfaceKind::Other,
+        None => match read_attr(dir, "tun_flags") {
+            Some(flags) => {
+                let flags = u32::from_str_radix(flags.trim_start_matches("0x"), 16).unwrap_or(0);
+                match flags & IFF_TAP {
+                    0 => InterfaceKind::Tun,
+                    _ => InterfaceKind::Tap,
+                }
+            }
+            None if arphrd.as_deref() == Some(ARPHRD_ETHER) && is_veth(dir) => InterfaceKind::Veth,
+            None => InterfaceKind::Other,
+        },
+    }
+}
+
+/// Return the [InterfaceKind] of the interface `name`, None if it's not listed in /sys/class/net.
+///
+/// [InterfaceKind]: ../network/enum.InterfaceKind.html
+pub(crate) fn read_interface_kind(root: &SysRoot, name: &str) -> Option<InterfaceKind> {
+    let dir = root.sys_path(format!("class/net/{}", name));
+    match dir.exists() {
+        true => Some(detect_kind(&dir)),
+        false => None,
+    }
+}
+
+/// Return the number of leading ones of a netmask.
+fn prefix_len(netmask: &[u8]) -> u8 {
+    netmask.iter().map(|byte| byte.count_ones() as u8).sum()
+}
+
+/// Get the IPv4 and IPv6 addresses of each interface using getifaddrs.
+fn get_addresses() -> Result<HashMap<String, Vec<InterfaceAddr>>, Error> {
+    let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut ifap) } == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    let mut addresses: HashMap<String, Vec<InterfaceAddr>> = HashMap::new();
+    let mut ifa = ifap;
+    while let Some(entry) = unsafe { ifa.as_ref() } {
+        ifa = entry.ifa_next;
+        let (addr, netmask) = match unsafe { (entry.ifa_addr.as_ref(), entry.ifa_netmask.as_ref()) }
+        {
+            (Some(addr), netmask) => (addr, netmask),
+            _ => continue,
+        };
+
+        let address = match addr.sa_family as i32 {
+            libc::AF_INET => {
+                let addr = unsafe { &*(addr as *const _ as *const libc::sockaddr_in) };
+                let netmask =
+                    netmask.map(|mask| unsafe { &*(mask as *const _ as *const libc::sockaddr_in) });
+                InterfaceAddr {
+                    addr: IpAddr::V4(Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr))),
+                    prefix_len: netmask
+                        .map_or(0, |mask| prefix_len(&mask.sin_addr.s_addr.to_ne_bytes())),
+                }
+            }
+            libc::AF_INET6 => {
+                let addr = unsafe { &*(addr as *const _ as *const libc::sockaddr_in6) };
+                let netmask = netmask
+                    .map(|mask| unsafe { &*(mask as *const _ as *const libc::sockaddr_in6) });
+                InterfaceAddr {
+                    addr: IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.s6_addr)),
+                    prefix_len: netmask.map_or(0, |mask| prefix_len(&mask.sin6_addr.s6_addr)),
+                }
+            }
+            // Link layer (AF_PACKET) entries
+            _ => continue,
+        };
+
+        let name = unsafe { CStr::from_ptr(entry.ifa_name) }
+            .to_string_lossy()
+            .into_owned();
+        addresses.entry(name).or_default().push(address);
+    }
+    unsafe { libc::freeifaddrs(ifap) };
+
+    Ok(addresses)
+}
+
+/// Read the [Interface] located at `dir`, None if it disappeared in the meantime.
+fn read_interface(
+    root: &SysRoot,
+    dir: &Path,
+    name: String,
+    addresses: &mut HashMap<String, Vec<InterfaceAddr>>,
+) -> Result<Option<Interface>, Error> {
+    // The interface may be removed between any of these reads
+    let required = parse_attr::<u32>(dir, "ifindex")
+        .and_then(|index| Ok((index, parse_attr::<u32>(dir, "mtu")?)));
+    let (index, mtu) = match required {
+        Ok(required) => required,
+        Err(Error::Io(err)) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    Ok(Some(Interface {
+        index,
+        // The address is empty for the interfaces without link layer address
+        mac: read_attr(dir, "address").filter(|mac| !mac.is_empty()),
+        mtu,
+        operstate: read_attr(dir, "operstate").unwrap_or_else(|| "unknown".to_owned()),
+        carrier: read_attr(dir, "carrier").map(|carrier| carrier == "1"),
+        duplex: read_attr(dir, "duplex").filter(|duplex| duplex != "unknown"),
+        // -1 (or u32::MAX on older kernels) when unknown
+        speed: read_attr(dir, "speed")
+            .and_then(|speed| speed.parse::<u64>().ok())
+            .filter(|speed| *speed != u32::MAX as u64),
+        addresses: addresses.remove(&name).unwrap_or_default(),
+        kind: detect_kind(dir),
+        is_virtual: root
+            .sys_path(format!("devices/virtual/net/{}", name))
+            .exists(),
+        name,
+    }))
+}
+
+/// Get the [Interface] description of each network interface, sorted by index.
+///
+/// The interfaces removed while being read are skipped.
+///
+/// [Interface]: ../network/struct.Interface.html
+pub fn get_interfaces() -> Result<Vec<Interface>, Error> {
+    get_interfaces_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the [Interface] description of each network interface reading from the given [SysRoot].
+///
+/// Note that the addresses are still those of the interfaces of the current network namespace.
+///
+/// [Interface]: ../network/struct.Interface.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_interfaces_with_root(root: &SysRoot) -> Result<Vec<Interface>, Error> {
+    let mut addresses = get_addresses()?;
+    let mut v_interfaces: Vec<Interface> = Vec::new();
+
+    for entry in fs::read_dir(root.sys_path("class/net"))? {
+        let entry = entry?;
+        // Skip the bonding_masters file
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if let Some(interface) = read_interface(root, &entry.path(), name, &mut addresses)? {
+            v_interfaces.push(interface);
+        }
+    }
+
+    v_interfaces.sort_unstable_by_key(|interface| interface.index);
+
+    Ok(v_interfaces)
+}
@@ -0,0 +1,116 @@
+use crate::network::{self, IoNet, IoNetRate, IoNetSample};
+use crate::{read_and_trim, Error, SysRoot};
+
+use std::{collections::HashMap, time::Instant};
+
+/// What we keep of an interface between two samples.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    // Used to detect a renamed interface, None if it's gone from sysfs
+    ifindex: Option<u32>,
+    ionet: IoNet,
+}
+
+/// Stateful sampler computing the [IoNetRate] of each interface between each call to [sample].
+///
+/// It keeps the previous [IoNet] of each interface keyed by its name, along with its
+/// index (from /sys/class/net/[interface]/ifindex) to detect the renamed interfaces.
+///
+/// [IoNetRate]: ../network/struct.IoNetRate.html
+/// [IoNet]: ../network/struct.IoNet.html
+/// [sample]: #method.sample
+#[derive(Debug, Clone)]
+pub struct IoNetSampler {
+    root: SysRoot,
+    prev: HashMap<String, Snapshot>,
+    prev_instant: Instant,
+}
+
+impl IoNetSampler {
+    /// Create a new sampler and take the initial snapshot.
+    pub fn new() -> Result<Self, Error> {
+        Self::with_root(SysRoot::default())
+    }
+
+    /// Create a new sampler reading from the given [SysRoot] and take the initial snapshot.
+    ///
+    /// [SysRoot]: ../struct.SysRoot.html
+    pub fn with_root(root: SysRoot) -> Result<Self, Error> {
+        Ok(IoNetSampler {
+            prev: snapshots(&root)?,
+            prev_instant: Instant::now(),
+            root,
+        })
+    }
+
+    /// Take a new snapshot and return the [IoNetSample] since the previous one.
+    ///
+    /// [IoNetSample]: ../network/struct.IoNetSample.html
+    pub fn sample(&mut self) -> Result<IoNetSample, Error> {
+        let curr = snapshots(&self.root)?;
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.prev_instant);
+
+        let mut removed: Vec<&String> = self
+            .prev
+            .keys()
+            .filter(|name| !curr.contains_key(*name))
+            .collect();
+        removed.sort_unstable();
+        let mut names: Vec<&String> = curr.keys().collect();
+        names.sort_unstable();
+
+        let mut sample = IoNetSample {
+            ionets: Vec::with_capacity(curr.len()),
+            added: Vec::new(),
+            removed: Vec::new(),
+            renamed: Vec::new(),
+            reset: Vec::new(),
+        };
+        for name in names {
+            let snapshot = &curr[name];
+            let prev = match self.prev.get(name) {
+                Some(prev) => prev,
+                // A removed interface with the same index is the same one renamed
+                None => match removed.iter().position(|old| {
+                    snapshot.ifindex.is_some() && self.prev[*old].ifindex == snapshot.ifindex
+                }) {
+                    Some(idx) => {
+                        let old = removed.remove(idx);
+                        sample.renamed.push((old.to_owned(), name.to_owned()));
+                        &self.prev[old]
+                    }
+                    None => {
+                        sample.added.push(name.to_owned());
+                        continue;
+                    }
+                },
+            };
+
+            match IoNetRate::from_delta(&prev.ionet, &snapshot.ionet, elapsed) {
+                Some(rate) => sample.ionets.push(rate),
+                None => sample.reset.push(name.to_owned()),
+            }
+        }
+        sample.removed = removed.into_iter().cloned().collect();
+
+        self.prev = curr;
+        self.prev_instant = now;
+
+        Ok(sample)
+    }
+}
+
+/// Take a [Snapshot] of each interface.
+fn snapshots(root: &SysRoot) -> Result<HashMap<String, Snapshot>, Error> {
+    Ok(network::get_ionets_with_root(root)?
+        .into_iter()
+        .map(|ionet| {
+            let ifindex =
+                read_and_trim(root.sys_path(format!("class/net/{}/ifindex", ionet.interface)))
+                    .ok()
+                    .and_then(|ifindex| ifindex.parse::<u32>().ok());
+            (ionet.interface.clone(), Snapshot { ifindex, ionet })
+        })
+        .collect())
+}
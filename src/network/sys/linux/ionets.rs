@@ -1,22 +1,24 @@
-use crate::network::IoNet;
+use super::interfaces::read_interface_kind;
+use crate::network::{InterfaceKind, IoNet};
+use crate::{Error, SysRoot, DEFAULT_ROOT};
 
 use std::{
     fs::File,
-    io::{BufRead, BufReader, Error, ErrorKind},
-    path::Path,
+    io::{BufRead, BufReader},
 };
 
-#[inline]
-fn _get_ionets(physical: bool) -> Result<Vec<IoNet>, Error> {
-    let file = File::open("/proc/net/dev")?;
+/// Parse the [IoNet] of each interfaces from the content of /proc/net/dev.
+///
+/// [IoNet]: ../network/struct.IoNet.html
+pub fn parse_ionets<R: BufRead>(mut reader: R) -> Result<Vec<IoNet>, Error> {
     let mut v_ionets: Vec<IoNet> = Vec::new();
-    let mut file = BufReader::with_capacity(2048, file);
 
-    let mut line_skip = 0;
+    let mut lineno = 0;
     let mut line = String::with_capacity(256);
-    while file.read_line(&mut line)? != 0 {
-        line_skip += 1;
-        if line_skip < 3 {
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        // Skip the two header lines
+        if lineno < 3 {
             line.clear();
             continue;
         }
@@ -29,42 +31,37 @@ fn _get_ionets(physical: bool) -> Result<Vec<IoNet>, Error> {
             }
         };
 
-        if physical && Path::new(&format!("/sys/devices/virtual/net/{}", interface)).exists() {
-            line.clear();
-            continue;
-        }
-
         let interface = interface.to_owned();
         let mut parts = match parts.next() {
             Some(rest) => rest.split_whitespace(),
-            None => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "Fatal error, /proc/net/dev parsing failed",
-                ));
-            }
+            None => return Err(Error::parse("/proc/net/dev", lineno)),
+        };
+        let mut next = || -> Result<u64, Error> {
+            parts
+                .next()
+                .unwrap_or("0")
+                .parse::<u64>()
+                .map_err(|_| Error::parse("/proc/net/dev", lineno))
         };
-
-        let rx_bytes = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let rx_packets = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let rx_errs = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let rx_drop = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let mut parts = parts.skip(4);
-        let tx_bytes = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let tx_packets = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let tx_errs = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let tx_drop = parts.next().unwrap_or("0").parse::<u64>().unwrap();
 
         v_ionets.push(IoNet {
             interface,
-            rx_bytes,
-            rx_packets,
-            rx_errs,
-            rx_drop,
-            tx_bytes,
-            tx_packets,
-            tx_errs,
-            tx_drop,
+            rx_bytes: next()?,
+            rx_packets: next()?,
+            rx_errs: next()?,
+            rx_drop: next()?,
+            rx_fifo: next()?,
+            rx_frame: next()?,
+            rx_compressed: next()?,
+            rx_multicast: next()?,
+            tx_bytes: next()?,
+            tx_packets: next()?,
+            tx_errs: next()?,
+            tx_drop: next()?,
+            tx_fifo: next()?,
+            tx_colls: next()?,
+            tx_carrier: next()?,
+            tx_compressed: next()?,
         });
         line.clear();
     }
@@ -72,16 +69,54 @@ fn _get_ionets(physical: bool) -> Result<Vec<IoNet>, Error> {
     Ok(v_ionets)
 }
 
+#[inline]
+fn _get_ionets(root: &SysRoot, physical: bool) -> Result<Vec<IoNet>, Error> {
+    let file = File::open(root.proc_path("net/dev"))?;
+    let mut v_ionets = parse_ionets(BufReader::with_capacity(2048, file))?;
+
+    if physical {
+        v_ionets.retain(|ionet| match read_interface_kind(root, &ionet.interface) {
+            Some(kind) => kind == InterfaceKind::Physical || kind == InterfaceKind::Wireless,
+            None => !root
+                .sys_path(format!("devices/virtual/net/{}", ionet.interface))
+                .exists(),
+        });
+    }
+
+    Ok(v_ionets)
+}
+
 /// Return the [IoNet] struct.
 ///
 /// [IoNet]: ../network/struct.IoNet.html
 pub fn get_ionets() -> Result<Vec<IoNet>, Error> {
-    _get_ionets(false)
+    _get_ionets(&DEFAULT_ROOT, false)
+}
+
+/// Return the [IoNet] struct reading from the given [SysRoot].
+///
+/// [IoNet]: ../network/struct.IoNet.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_ionets_with_root(root: &SysRoot) -> Result<Vec<IoNet>, Error> {
+    _get_ionets(root, false)
 }
 
 /// Return the [IoNet] struct but keeping only those from Physical Interfaces.
 ///
+/// The physical interfaces are the wired and wireless ones backed by a device,
+/// see [InterfaceKind].
+///
+/// [InterfaceKind]: ../network/enum.InterfaceKind.html
 /// [IoNet]: ../network/struct.IoNet.html
 pub fn get_physical_ionets() -> Result<Vec<IoNet>, Error> {
-    _get_ionets(true)
+    _get_ionets(&DEFAULT_ROOT, true)
+}
+
+/// Return the [IoNet] struct but keeping only those from Physical Interfaces
+/// reading from the given [SysRoot].
+///
+/// [IoNet]: ../network/struct.IoNet.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_physical_ionets_with_root(root: &SysRoot) -> Result<Vec<IoNet>, Error> {
+    _get_ionets(root, true)
 }
@@ -1,3 +1,7 @@
+mod interfaces;
+mod ionet_sampler;
 mod ionets;
 
+pub use interfaces::*;
+pub use ionet_sampler::*;
 pub use ionets::*;
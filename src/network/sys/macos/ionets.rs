@@ -1,9 +1,9 @@
 // Based on https://github.com/heim-rs/heim/blob/master/heim-net/src/sys/macos/bindings.rs
 use crate::binding::{if_msghdr2, if_msghdr_partial};
 use crate::network::IoNet;
+use crate::Error;
 
 use libc::sysctl;
-use std::io::Error;
 use std::mem;
 use std::ptr;
 
@@ -138,6 +138,9 @@ pub fn get_ionets() -> Result<Vec<IoNet>, Error> {
                 tx_packets: msg.ifm_data.ifi_opackets as u64,
                 tx_errs: msg.ifm_data.ifi_oerrors as u64,
                 tx_drop: msg.ifm_snd_drops as u64, // Not sure about this one, can't find enough doc
+                rx_multicast: msg.ifm_data.ifi_imcasts as u64,
+                tx_colls: msg.ifm_data.ifi_collisions as u64,
+                ..Default::default()
             })
         })
         .collect()
@@ -0,0 +1,47 @@
+mod sys;
+
+pub use sys::*;
+
+use serde::Serialize;
+
+/// Resource tracked by the Pressure Stall Information (PSI).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum PressureResource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl PressureResource {
+    /// Name of the resource as used by the pressure files (eg: cpu for cpu.pressure).
+    pub fn name(&self) -> &'static str {
+        match self {
+            PressureResource::Cpu => "cpu",
+            PressureResource::Memory => "memory",
+            PressureResource::Io => "io",
+        }
+    }
+}
+
+/// Struct containing the share of time some (or all) tasks were stalled on a resource.
+///
+/// The averages are percentages over the last 10, 60 and 300 seconds,
+/// `total` is the total stall time in microseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PressureAvg {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    pub total: u64,
+}
+
+/// Struct containing the Pressure Stall Information of a resource.
+///
+/// `some` is the time at least one task was stalled, `full` the time all non-idle
+/// tasks were stalled at the same time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Pressure {
+    pub some: PressureAvg,
+    /// Not reported for the cpu before Linux 5.13
+    pub full: Option<PressureAvg>,
+}
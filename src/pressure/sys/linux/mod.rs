@@ -0,0 +1,3 @@
+mod pressure;
+
+pub use pressure::*;
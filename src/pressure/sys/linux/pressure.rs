@@ -0,0 +1,93 @@
+use crate::pressure::{Pressure, PressureAvg, PressureResource};
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs,
+    io::{BufRead, ErrorKind},
+    path::Path,
+};
+
+/// Parse the [Pressure] from the content of /proc/pressure/{cpu,memory,io}
+/// or of the cgroup v2 {cpu,memory,io}.pressure files.
+///
+/// [Pressure]: ../pressure/struct.Pressure.html
+pub fn parse_pressure<R: BufRead>(mut reader: R) -> Result<Pressure, Error> {
+    let (mut some, mut full) = (None, None);
+    let mut lineno = 0;
+    let mut line = String::with_capacity(64);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let err = || Error::parse("pressure", lineno);
+        // some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+        let mut fields = line.split_whitespace();
+        let field = match fields.next() {
+            Some("some") => &mut some,
+            Some("full") => &mut full,
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+
+        let mut avg = PressureAvg::default();
+        for field in fields {
+            let mut parts = field.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some("avg10"), Some(value)) => avg.avg10 = value.parse().map_err(|_| err())?,
+                (Some("avg60"), Some(value)) => avg.avg60 = value.parse().map_err(|_| err())?,
+                (Some("avg300"), Some(value)) => avg.avg300 = value.parse().map_err(|_| err())?,
+                (Some("total"), Some(value)) => avg.total = value.parse().map_err(|_| err())?,
+                (Some(_), Some(_)) => {}
+                _ => return Err(err()),
+            }
+        }
+        *field = Some(avg);
+        line.clear();
+    }
+
+    Ok(Pressure {
+        some: some.ok_or(Error::MissingField("some"))?,
+        full,
+    })
+}
+
+/// Read the [Pressure] from the file at `path`.
+///
+/// Return [Error::Unsupported] if the kernel is built without PSI (< 4.20)
+/// or if it's disabled (psi=0).
+///
+/// [Pressure]: ../pressure/struct.Pressure.html
+/// [Error::Unsupported]: ../enum.Error.html#variant.Unsupported
+pub(crate) fn read_pressure(path: &Path) -> Result<Pressure, Error> {
+    let unsupported = |err: std::io::Error| match err.kind() {
+        ErrorKind::NotFound => Error::Unsupported("PSI"),
+        // The files still exist when PSI is disabled at boot
+        _ if err.raw_os_error() == Some(libc::EOPNOTSUPP) => Error::Unsupported("PSI"),
+        _ => Error::from_io(err, path.to_string_lossy()),
+    };
+
+    let content = fs::read_to_string(path).map_err(unsupported)?;
+
+    parse_pressure(content.as_bytes())
+}
+
+/// Get the system-wide [Pressure] of the `resource`.
+///
+/// Return [Error::Unsupported] on kernels without PSI (< 4.20 or booted with psi=0).
+///
+/// [Pressure]: ../pressure/struct.Pressure.html
+/// [Error::Unsupported]: ../enum.Error.html#variant.Unsupported
+pub fn get_pressure(resource: PressureResource) -> Result<Pressure, Error> {
+    get_pressure_with_root(&DEFAULT_ROOT, resource)
+}
+
+/// Get the system-wide [Pressure] of the `resource` reading from the given [SysRoot].
+///
+/// [Pressure]: ../pressure/struct.Pressure.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_pressure_with_root(
+    root: &SysRoot,
+    resource: PressureResource,
+) -> Result<Pressure, Error> {
+    read_pressure(&root.proc_path(format!("pressure/{}", resource.name())))
+}
@@ -0,0 +1,167 @@
+mod sys;
+
+pub use sys::*;
+
+use serde::Serialize;
+
+/// State of a process as reported by the kernel.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum ProcessState {
+    /// R
+    Running,
+    /// S
+    Sleeping,
+    /// D
+    DiskSleep,
+    /// Z
+    Zombie,
+    /// T
+    Stopped,
+    /// t
+    TracingStop,
+    /// X (or x before Linux 3.13)
+    Dead,
+    /// I (since Linux 4.14)
+    Idle,
+    /// Any other state code (K, W, P, ... on older kernels)
+    Unknown(char),
+}
+
+impl From<char> for ProcessState {
+    fn from(state: char) -> Self {
+        match state {
+            'R' => ProcessState::Running,
+            'S' => ProcessState::Sleeping,
+            'D' => ProcessState::DiskSleep,
+            'Z' => ProcessState::Zombie,
+            'T' => ProcessState::Stopped,
+            't' => ProcessState::TracingStop,
+            'X' | 'x' => ProcessState::Dead,
+            'I' => ProcessState::Idle,
+            other => ProcessState::Unknown(other),
+        }
+    }
+}
+
+/// Struct containing a process' information.
+///
+/// `start_time` is in seconds since the epoch, `rss` and `vsz` are in bytes and
+/// `start_ticks`/`utime`/`stime` are in clock ticks (see [clock_ticks]).
+///
+/// [clock_ticks]: ../fn.clock_ticks.html
+#[derive(Debug, Clone, Serialize)]
+pub struct Process {
+    pub pid: i32,
+    pub ppid: i32,
+    pub name: String,
+    /// Empty for kernel threads and zombies
+    pub cmdline: Vec<String>,
+    pub state: ProcessState,
+    /// Real user ID
+    pub uid: u32,
+    /// Real group ID
+    pub gid: u32,
+    pub threads: u64,
+    pub start_time: u64,
+    /// Start time since boot, unlike `start_time` it doesn't depend on the
+    /// boot time reported by the kernel which can shift by a second
+    pub start_ticks: u64,
+    pub rss: u64,
+    pub vsz: u64,
+    pub utime: u64,
+    pub stime: u64,
+}
+
+/// Struct containing the usage of a process between two samples
+/// of a [ProcessSampler].
+///
+/// `cpu_usage` is a percentage of the whole system (100% meaning all the logical
+/// cpus were busy running the process), `rss_delta` is in bytes.
+/// Both are None for a process which appeared since the previous sample.
+///
+/// [ProcessSampler]: ../process/struct.ProcessSampler.html
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessUsage {
+    pub process: Process,
+    pub cpu_usage: Option<f64>,
+    pub rss_delta: Option<i64>,
+}
+
+/// Struct containing the [ProcessUsage] of each running process as returned
+/// by a [ProcessSampler], along with the pids which appeared or exited since
+/// the previous sample.
+///
+/// [ProcessUsage]: ../process/struct.ProcessUsage.html
+/// [ProcessSampler]: ../process/struct.ProcessSampler.html
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessSample {
+    pub processes: Vec<ProcessUsage>,
+    pub appeared: Vec<i32>,
+    pub exited: Vec<i32>,
+}
+
+/// Struct containing the I/O counters of a process (from /proc/[pid]/io).
+///
+/// `rchar`/`wchar` are the bytes passed to read/write syscalls (including the page cache),
+/// `read_bytes`/`write_bytes` are the bytes really fetched from/sent to the storage layer.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProcessIo {
+    pub rchar: u64,
+    pub wchar: u64,
+    pub syscr: u64,
+    pub syscw: u64,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    pub cancelled_write_bytes: u64,
+}
+
+impl ProcessIo {
+    /// Compute the [ProcessIo] counters between two snapshots.
+    ///
+    /// Return None if any counter went backward (eg: the pid was reused).
+    pub fn from_delta(prev: &ProcessIo, curr: &ProcessIo) -> Option<ProcessIo> {
+        Some(ProcessIo {
+            rchar: curr.rchar.checked_sub(prev.rchar)?,
+            wchar: curr.wchar.checked_sub(prev.wchar)?,
+            syscr: curr.syscr.checked_sub(prev.syscr)?,
+            syscw: curr.syscw.checked_sub(prev.syscw)?,
+            read_bytes: curr.read_bytes.checked_sub(prev.read_bytes)?,
+            write_bytes: curr.write_bytes.checked_sub(prev.write_bytes)?,
+            cancelled_write_bytes: curr
+                .cancelled_write_bytes
+                .checked_sub(prev.cancelled_write_bytes)?,
+        })
+    }
+}
+
+/// Struct containing the soft and hard values of a resource limit.
+///
+/// A None value means the resource is unlimited.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+pub struct Limit {
+    pub soft: Option<u64>,
+    pub hard: Option<u64>,
+}
+
+/// Struct containing the resource limits of a process (from /proc/[pid]/limits).
+///
+/// See `man 2 getrlimit` for the meaning and the unit of each resource.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProcessLimits {
+    pub cpu_time: Limit,
+    pub file_size: Limit,
+    pub data_size: Limit,
+    pub stack_size: Limit,
+    pub core_file_size: Limit,
+    pub resident_set: Limit,
+    pub processes: Limit,
+    pub open_files: Limit,
+    pub locked_memory: Limit,
+    pub address_space: Limit,
+    pub file_locks: Limit,
+    pub pending_signals: Limit,
+    pub msgqueue_size: Limit,
+    pub nice_priority: Limit,
+    pub realtime_priority: Limit,
+    pub realtime_timeout: Limit,
+}
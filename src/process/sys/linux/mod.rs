@@ -0,0 +1,9 @@
+mod process_fds;
+mod process_io;
+mod process_sampler;
+mod processes;
+
+pub use process_fds::*;
+pub use process_io::*;
+pub use process_sampler::*;
+pub use processes::*;
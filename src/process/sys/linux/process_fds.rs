@@ -0,0 +1,150 @@
+use crate::process::{Limit, ProcessLimits};
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader},
+};
+
+const LIMITS: [&str; 16] = [
+    "Max cpu time",
+    "Max file size",
+    "Max data size",
+    "Max stack size",
+    "Max core file size",
+    "Max resident set",
+    "Max processes",
+    "Max open files",
+    "Max locked memory",
+    "Max address space",
+    "Max file locks",
+    "Max pending signals",
+    "Max msgqueue size",
+    "Max nice priority",
+    "Max realtime priority",
+    "Max realtime timeout",
+];
+
+/// Parse the [ProcessLimits] from the content of /proc/[pid]/limits.
+///
+/// [ProcessLimits]: ../process/struct.ProcessLimits.html
+pub fn parse_process_limits<R: BufRead>(mut reader: R) -> Result<ProcessLimits, Error> {
+    // Keep track of the fields we've found
+    let mut matched = [false; 16];
+    let mut limits = ProcessLimits::default();
+    let mut lineno = 0;
+    let mut line = String::with_capacity(128);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        // The name of the resource contains spaces, the values start at the first
+        // numeric (or unlimited) column: `Max open files    1024    524288    files`
+        let mut name = String::with_capacity(32);
+        let mut values = Vec::with_capacity(2);
+        for field in line.split_whitespace() {
+            if values.is_empty()
+                && field != "unlimited"
+                && !field.bytes().all(|b| b.is_ascii_digit())
+            {
+                if !name.is_empty() {
+                    name.push(' ');
+                }
+                name.push_str(field);
+            } else if values.len() < 2 {
+                values.push(field);
+            }
+        }
+
+        let idx = match LIMITS.iter().position(|limit| *limit == name) {
+            Some(idx) => idx,
+            None => {
+                line.clear();
+                continue;
+            }
+        };
+        let parse = |value: &str| match value {
+            "unlimited" => Ok(None),
+            value => value
+                .parse::<u64>()
+                .map(Some)
+                .map_err(|_| Error::parse("/proc/[pid]/limits", lineno)),
+        };
+        let limit = match values[..] {
+            [soft, hard] => Limit {
+                soft: parse(soft)?,
+                hard: parse(hard)?,
+            },
+            _ => return Err(Error::parse("/proc/[pid]/limits", lineno)),
+        };
+
+        let field = match idx {
+            0 => &mut limits.cpu_time,
+            1 => &mut limits.file_size,
+            2 => &mut limits.data_size,
+            3 => &mut limits.stack_size,
+            4 => &mut limits.core_file_size,
+            5 => &mut limits.resident_set,
+            6 => &mut limits.processes,
+            7 => &mut limits.open_files,
+            8 => &mut limits.locked_memory,
+            9 => &mut limits.address_space,
+            10 => &mut limits.file_locks,
+            11 => &mut limits.pending_signals,
+            12 => &mut limits.msgqueue_size,
+            13 => &mut limits.nice_priority,
+            14 => &mut limits.realtime_priority,
+            _ => &mut limits.realtime_timeout,
+        };
+        *field = limit;
+        matched[idx] = true;
+
+        line.clear();
+    }
+
+    match matched.iter().position(|m| !*m) {
+        Some(missing) => Err(Error::MissingField(LIMITS[missing])),
+        None => Ok(limits),
+    }
+}
+
+/// Get the number of open file descriptors of the process `pid`.
+///
+/// Listing the file descriptors of a process owned by another user requires
+/// privileges, [Error::PermissionDenied] is returned otherwise.
+///
+/// [Error::PermissionDenied]: ../enum.Error.html#variant.PermissionDenied
+pub fn get_fd_count(pid: i32) -> Result<u64, Error> {
+    get_fd_count_with_root(&DEFAULT_ROOT, pid)
+}
+
+/// Get the number of open file descriptors of the process `pid` reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_fd_count_with_root(root: &SysRoot, pid: i32) -> Result<u64, Error> {
+    let path = root.proc_path(format!("{}/fd", pid));
+    let entries = fs::read_dir(&path).map_err(|err| Error::from_io(err, path.to_string_lossy()))?;
+
+    let mut count = 0;
+    for entry in entries {
+        entry?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Get the [ProcessLimits] of the process `pid`.
+///
+/// [ProcessLimits]: ../process/struct.ProcessLimits.html
+pub fn get_process_limits(pid: i32) -> Result<ProcessLimits, Error> {
+    get_process_limits_with_root(&DEFAULT_ROOT, pid)
+}
+
+/// Get the [ProcessLimits] of the process `pid` reading from the given [SysRoot].
+///
+/// [ProcessLimits]: ../process/struct.ProcessLimits.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_process_limits_with_root(root: &SysRoot, pid: i32) -> Result<ProcessLimits, Error> {
+    let path = root.proc_path(format!("{}/limits", pid));
+    let file = File::open(&path).map_err(|err| Error::from_io(err, path.to_string_lossy()))?;
+    parse_process_limits(BufReader::with_capacity(2048, file))
+}
@@ -0,0 +1,81 @@
+use crate::process::ProcessIo;
+use crate::{Error, SysRoot, DEFAULT_ROOT};
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+/// Parse the [ProcessIo] from the content of /proc/[pid]/io.
+///
+/// [ProcessIo]: ../process/struct.ProcessIo.html
+pub fn parse_process_io<R: BufRead>(mut reader: R) -> Result<ProcessIo, Error> {
+    // Keep track of the fields we've found
+    let mut matched = [false; 7];
+    let mut process_io = ProcessIo::default();
+    let mut lineno = 0;
+    let mut line = String::with_capacity(64);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let mut parts = line.splitn(2, ':');
+        let (idx, field) = match parts.next() {
+            Some("rchar") => (0, &mut process_io.rchar),
+            Some("wchar") => (1, &mut process_io.wchar),
+            Some("syscr") => (2, &mut process_io.syscr),
+            Some("syscw") => (3, &mut process_io.syscw),
+            Some("read_bytes") => (4, &mut process_io.read_bytes),
+            Some("write_bytes") => (5, &mut process_io.write_bytes),
+            Some("cancelled_write_bytes") => (6, &mut process_io.cancelled_write_bytes),
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+
+        *field = parts
+            .next()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .ok_or_else(|| Error::parse("/proc/[pid]/io", lineno))?;
+        matched[idx] = true;
+
+        line.clear();
+    }
+
+    let names = [
+        "rchar",
+        "wchar",
+        "syscr",
+        "syscw",
+        "read_bytes",
+        "write_bytes",
+        "cancelled_write_bytes",
+    ];
+    match matched.iter().position(|m| !*m) {
+        Some(missing) => Err(Error::MissingField(names[missing])),
+        None => Ok(process_io),
+    }
+}
+
+/// Get the [ProcessIo] counters of the process `pid`.
+///
+/// Reading the counters of a process owned by another user requires
+/// privileges (CAP_SYS_PTRACE), [Error::PermissionDenied] is returned otherwise.
+///
+/// [ProcessIo]: ../process/struct.ProcessIo.html
+/// [Error::PermissionDenied]: ../enum.Error.html#variant.PermissionDenied
+pub fn get_process_io(pid: i32) -> Result<ProcessIo, Error> {
+    get_process_io_with_root(&DEFAULT_ROOT, pid)
+}
+
+/// Get the [ProcessIo] counters of the process `pid` reading from the given [SysRoot].
+///
+/// [ProcessIo]: ../process/struct.ProcessIo.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_process_io_with_root(root: &SysRoot, pid: i32) -> Result<ProcessIo, Error> {
+    let path = root.proc_path(format!("{}/io", pid));
+    let file = File::open(&path).map_err(|err| Error::from_io(err, path.to_string_lossy()))?;
+    parse_process_io(BufReader::with_capacity(256, file)).map_err(|err| match err {
+        Error::Io(err) => Error::from_io(err, path.to_string_lossy()),
+        err => err,
+    })
+}
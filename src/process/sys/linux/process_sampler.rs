@@ -0,0 +1,126 @@
+use crate::cpu;
+use crate::process::{self, Process, ProcessSample, ProcessUsage};
+use crate::{Error, SysRoot, CLOCK_TICKS};
+
+use std::{collections::HashMap, time::Instant};
+
+/// What we keep of a [Process] between two samples.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    // Used to detect a pid being reused by another process
+    start_ticks: u64,
+    ticks: u64,
+    rss: u64,
+}
+
+impl From<&Process> for Snapshot {
+    fn from(process: &Process) -> Self {
+        Snapshot {
+            start_ticks: process.start_ticks,
+            ticks: process.utime + process.stime,
+            rss: process.rss,
+        }
+    }
+}
+
+/// Stateful sampler computing the [ProcessUsage] of each process between each call to [sample].
+///
+/// It keeps the previous per-pid cpu ticks and RSS, the cpu usage is normalised
+/// to the number of logical cpus (see [get_logical_count]).
+///
+/// [ProcessUsage]: ../process/struct.ProcessUsage.html
+/// [get_logical_count]: ../cpu/fn.get_logical_count.html
+/// [sample]: #method.sample
+#[derive(Debug, Clone)]
+pub struct ProcessSampler {
+    root: SysRoot,
+    logical_count: u32,
+    prev: HashMap<i32, Snapshot>,
+    prev_instant: Instant,
+}
+
+impl ProcessSampler {
+    /// Create a new sampler and take the initial snapshot.
+    pub fn new() -> Result<Self, Error> {
+        Self::with_root(SysRoot::default())
+    }
+
+    /// Create a new sampler reading from the given [SysRoot] and take the initial snapshot.
+    ///
+    /// [SysRoot]: ../struct.SysRoot.html
+    pub fn with_root(root: SysRoot) -> Result<Self, Error> {
+        let prev = process::get_processes_with_root(&root)?
+            .iter()
+            .map(|process| (process.pid, Snapshot::from(process)))
+            .collect();
+
+        Ok(ProcessSampler {
+            logical_count: cpu::get_logical_count()?.max(1),
+            prev,
+            prev_instant: Instant::now(),
+            root,
+        })
+    }
+
+    /// Take a new snapshot and return the [ProcessSample] since the previous one.
+    ///
+    /// [ProcessSample]: ../process/struct.ProcessSample.html
+    pub fn sample(&mut self) -> Result<ProcessSample, Error> {
+        let processes = process::get_processes_with_root(&self.root)?;
+        let now = Instant::now();
+        // Number of ticks all the cpus could have spent since the previous sample
+        let elapsed = now.duration_since(self.prev_instant).as_secs_f64()
+            * *CLOCK_TICKS as f64
+            * self.logical_count as f64;
+
+        let mut curr: HashMap<i32, Snapshot> = HashMap::with_capacity(processes.len());
+        let mut appeared: Vec<i32> = Vec::new();
+        let mut usages: Vec<ProcessUsage> = Vec::with_capacity(processes.len());
+        for process in processes {
+            let snapshot = Snapshot::from(&process);
+            let (cpu_usage, rss_delta) = match self.prev.get(&process.pid) {
+                Some(prev) if prev.start_ticks == snapshot.start_ticks => {
+                    let ticks = snapshot.ticks.saturating_sub(prev.ticks) as f64;
+                    let cpu_usage = if elapsed > 0.0 {
+                        Some((ticks / elapsed * 100.0).min(100.0))
+                    } else {
+                        None
+                    };
+                    (cpu_usage, Some(snapshot.rss as i64 - prev.rss as i64))
+                }
+                _ => {
+                    appeared.push(process.pid);
+                    (None, None)
+                }
+            };
+
+            curr.insert(process.pid, snapshot);
+            usages.push(ProcessUsage {
+                process,
+                cpu_usage,
+                rss_delta,
+            });
+        }
+
+        // A reused pid is both an exited and an appeared process
+        let mut exited: Vec<i32> = self
+            .prev
+            .iter()
+            .filter(|(pid, prev)| match curr.get(pid) {
+                Some(snapshot) => snapshot.start_ticks != prev.start_ticks,
+                None => true,
+            })
+            .map(|(pid, _)| *pid)
+            .collect();
+        exited.sort_unstable();
+
+        self.prev = curr;
+        self.prev_instant = now;
+
+        Ok(ProcessSample {
+            processes: usages,
+            appeared,
+            exited,
+        })
+    }
+}
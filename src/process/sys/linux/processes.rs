@@ -0,0 +1,209 @@
+use crate::process::{Process, ProcessState};
+use crate::{Error, SysRoot, CLOCK_TICKS, DEFAULT_ROOT, PAGE_SIZE};
+
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader, ErrorKind},
+};
+
+/// Parse the boot time (in seconds since the epoch) from the content of /proc/stat.
+fn parse_btime<R: BufRead>(mut reader: R) -> Result<u64, Error> {
+    let mut lineno = 0;
+    let mut line = String::with_capacity(128);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        if let Some(btime) = line.strip_prefix("btime ") {
+            return btime
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| Error::parse("/proc/stat", lineno));
+        }
+        line.clear();
+    }
+
+    Err(Error::MissingField("btime"))
+}
+
+/// Parse the content of /proc/[pid]/stat, the uid, gid and cmdline are left empty.
+///
+/// See `man 5 proc` for the meaning of each fields.
+fn parse_stat(pid: i32, content: &str, btime: u64) -> Result<Process, Error> {
+    let err = || Error::parse(format!("/proc/{}/stat", pid), 1);
+
+    // The name is between parenthesis and may contains spaces or parenthesis itself
+    let start = content.find('(').ok_or_else(err)?;
+    let end = content.rfind(')').ok_or_else(err)?;
+    let name = content.get(start + 1..end).ok_or_else(err)?;
+
+    let mut fields = content[end + 1..].split_whitespace();
+    let parse = |value: &str| value.parse::<u64>().map_err(|_| err());
+
+    let state = nth!(fields, 0, "state")?;
+    let ppid = nth!(fields, 0, "ppid")?;
+    let utime = nth!(fields, 9, "utime")?;
+    let stime = nth!(fields, 0, "stime")?;
+    let threads = nth!(fields, 4, "num_threads")?;
+    let starttime = parse(nth!(fields, 1, "starttime")?)?;
+    let vsize = nth!(fields, 0, "vsize")?;
+    let rss = nth!(fields, 0, "rss")?;
+
+    Ok(Process {
+        pid,
+        ppid: ppid.parse::<i32>().map_err(|_| err())?,
+        name: name.to_owned(),
+        cmdline: Vec::new(),
+        state: ProcessState::from(state.chars().next().ok_or_else(err)?),
+        uid: 0,
+        gid: 0,
+        threads: parse(threads)?,
+        start_time: btime + starttime / *CLOCK_TICKS,
+        start_ticks: starttime,
+        // rss can be negative for some kernel threads
+        rss: rss.parse::<i64>().map_err(|_| err())?.max(0) as u64 * *PAGE_SIZE,
+        vsz: parse(vsize)?,
+        utime: parse(utime)?,
+        stime: parse(stime)?,
+    })
+}
+
+/// Parse the real uid and gid of the process from the content of /proc/[pid]/status.
+fn parse_status_ids<R: BufRead>(pid: i32, mut reader: R) -> Result<(u32, u32), Error> {
+    let (mut uid, mut gid) = (None, None);
+    let mut lineno = 0;
+    let mut line = String::with_capacity(64);
+    while reader.read_line(&mut line)? != 0 {
+        lineno += 1;
+        let field = match line.as_bytes().get(..4) {
+            Some(b"Uid:") => &mut uid,
+            Some(b"Gid:") => &mut gid,
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+
+        // Uid: real effective saved filesystem
+        *field = Some(
+            line[4..]
+                .split_whitespace()
+                .next()
+                .and_then(|value| value.parse::<u32>().ok())
+                .ok_or_else(|| Error::parse(format!("/proc/{}/status", pid), lineno))?,
+        );
+
+        if let (Some(uid), Some(gid)) = (uid, gid) {
+            return Ok((uid, gid));
+        }
+        line.clear();
+    }
+
+    match uid {
+        Some(_) => Err(Error::MissingField("Gid")),
+        None => Err(Error::MissingField("Uid")),
+    }
+}
+
+/// Split the content of /proc/[pid]/cmdline into its arguments.
+fn parse_cmdline(content: &[u8]) -> Vec<String> {
+    // Arguments are separated and terminated by a NUL byte
+    let content = content.strip_suffix(b"\0").unwrap_or(content);
+    if content.is_empty() {
+        return Vec::new();
+    }
+
+    content
+        .split(|b| *b == 0)
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect()
+}
+
+/// Return true if the error means the process has exited while we were reading it.
+fn is_gone(err: &Error) -> bool {
+    match err {
+        Error::Io(err) => {
+            err.kind() == ErrorKind::NotFound || err.raw_os_error() == Some(libc::ESRCH)
+        }
+        _ => false,
+    }
+}
+
+/// Return true if the error means the process can't be read by the current user
+/// (/proc mounted with hidepid, processes of other users on hardened kernels).
+fn is_denied(err: &Error) -> bool {
+    match err {
+        Error::PermissionDenied(_) => true,
+        Error::Io(err) => err.kind() == ErrorKind::PermissionDenied,
+        _ => false,
+    }
+}
+
+fn read_btime(root: &SysRoot) -> Result<u64, Error> {
+    let file = File::open(root.proc_path("stat"))?;
+    parse_btime(BufReader::with_capacity(1024, file))
+}
+
+fn read_process(root: &SysRoot, pid: i32, btime: u64) -> Result<Process, Error> {
+    let stat = fs::read_to_string(root.proc_path(format!("{}/stat", pid)))?;
+    let mut process = parse_stat(pid, &stat, btime)?;
+
+    let file = File::open(root.proc_path(format!("{}/status", pid)))?;
+    let (uid, gid) = parse_status_ids(pid, BufReader::with_capacity(2048, file))?;
+    process.uid = uid;
+    process.gid = gid;
+
+    process.cmdline = parse_cmdline(&fs::read(root.proc_path(format!("{}/cmdline", pid)))?);
+
+    Ok(process)
+}
+
+/// Get the [Process] info of the process `pid`.
+///
+/// [Process]: ../process/struct.Process.html
+pub fn get_process(pid: i32) -> Result<Process, Error> {
+    get_process_with_root(&DEFAULT_ROOT, pid)
+}
+
+/// Get the [Process] info of the process `pid` reading from the given [SysRoot].
+///
+/// [Process]: ../process/struct.Process.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_process_with_root(root: &SysRoot, pid: i32) -> Result<Process, Error> {
+    read_process(root, pid, read_btime(root)?)
+}
+
+/// Get the [Process] info of every running processes.
+///
+/// Processes exiting while being read, or which can't be read by the current
+/// user, are skipped.
+///
+/// [Process]: ../process/struct.Process.html
+pub fn get_processes() -> Result<Vec<Process>, Error> {
+    get_processes_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the [Process] info of every running processes reading from the given [SysRoot].
+///
+/// [Process]: ../process/struct.Process.html
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_processes_with_root(root: &SysRoot) -> Result<Vec<Process>, Error> {
+    let btime = read_btime(root)?;
+    let mut v_processes: Vec<Process> = Vec::new();
+
+    for entry in fs::read_dir(&root.proc)? {
+        // Only the numerical directories are processes
+        let pid = match entry?.file_name().to_str().map(|name| name.parse::<i32>()) {
+            Some(Ok(pid)) => pid,
+            _ => continue,
+        };
+
+        match read_process(root, pid, btime) {
+            Ok(process) => v_processes.push(process),
+            Err(err) if is_gone(&err) || is_denied(&err) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    v_processes.sort_unstable_by_key(|process| process.pid);
+
+    Ok(v_processes)
+}
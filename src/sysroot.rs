@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+/// Struct containing the roots of the filesystems read by the Linux collectors.
+///
+/// Every `get_*` function reading from /proc, /sys, /etc or /var has
+/// a `*_with_root` counterpart taking a [SysRoot], which allow to read from
+/// fixtures or from the host's filesystems bind-mounted inside a container.
+///
+/// Functions relying on syscalls (`sysinfo`, `uname`, `statvfs`, ...) are
+/// not affected by the [SysRoot].
+///
+/// ```no_run
+/// use sys_metrics::{cpu, SysRoot};
+///
+/// // The host's /proc, /sys, ... are mounted under /host
+/// let root = SysRoot::from_prefix("/host");
+/// let cputimes = cpu::get_cputimes_with_root(&root).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysRoot {
+    pub proc: PathBuf,
+    pub sys: PathBuf,
+    pub etc: PathBuf,
+    pub var: PathBuf,
+}
+
+impl Default for SysRoot {
+    fn default() -> Self {
+        SysRoot::from_prefix("/")
+    }
+}
+
+impl SysRoot {
+    /// Return a [SysRoot] where every root is located under `prefix`.
+    ///
+    /// `SysRoot::from_prefix("/host")` will read from /host/proc, /host/sys, ...
+    pub fn from_prefix<P>(prefix: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let prefix = prefix.as_ref();
+        SysRoot {
+            proc: prefix.join("proc"),
+            sys: prefix.join("sys"),
+            etc: prefix.join("etc"),
+            var: prefix.join("var"),
+        }
+    }
+
+    /// Return the path of `path` relative to the proc root.
+    pub fn proc_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.proc.join(path)
+    }
+
+    /// Return the path of `path` relative to the sys root.
+    pub fn sys_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.sys.join(path)
+    }
+
+    /// Return the path of `path` relative to the etc root.
+    pub fn etc_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.etc.join(path)
+    }
+
+    /// Return the path of `path` relative to the var root.
+    pub fn var_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.var.join(path)
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Default root used by the `get_*` functions (/proc, /sys, /etc and /var).
+    pub(crate) static ref DEFAULT_ROOT: SysRoot = SysRoot::default();
+}
@@ -1,11 +1,11 @@
 // Based on https://github.com/heim-rs/heim/blob/master/heim-virt/src/sys/linux/containers.rs
 
 use crate::virt::{err_not_found, Virtualization};
+use crate::{Error, SysRoot};
 
 use std::{
     fs::File,
-    io::{BufRead, BufReader, Error},
-    path::Path,
+    io::{BufRead, BufReader},
 };
 
 fn try_guess_container(value: &str) -> Result<Virtualization, Error> {
@@ -21,8 +21,8 @@ fn try_guess_container(value: &str) -> Result<Virtualization, Error> {
     }
 }
 
-pub(crate) fn detect_wsl() -> Result<Virtualization, Error> {
-    let line = std::fs::read_to_string("/proc/sys/kernel/osrelease")?;
+pub(crate) fn detect_wsl(root: &SysRoot) -> Result<Virtualization, Error> {
+    let line = std::fs::read_to_string(root.proc_path("sys/kernel/osrelease"))?;
 
     match line {
         ref probe if probe.contains("Microsoft") => Ok(Virtualization::Wsl),
@@ -31,11 +31,8 @@ pub(crate) fn detect_wsl() -> Result<Virtualization, Error> {
     }
 }
 
-pub(crate) fn detect_openvz() -> Result<Virtualization, Error> {
-    match (
-        Path::new("/proc/vz").exists(),
-        Path::new("/proc/bc").exists(),
-    ) {
+pub(crate) fn detect_openvz(root: &SysRoot) -> Result<Virtualization, Error> {
+    match (root.proc_path("vz").exists(), root.proc_path("bc").exists()) {
         // `/proc/vz` exists in container and outside of the container,
         // `/proc/bc` only outside of the container.
         (true, false) => Ok(Virtualization::OpenVz),
@@ -43,18 +40,19 @@ pub(crate) fn detect_openvz() -> Result<Virtualization, Error> {
     }
 }
 
-pub(crate) fn detect_systemd_container() -> Result<Virtualization, Error> {
-    // systemd PID 1 might have dropped this information into a file in `/run`.
+pub(crate) fn detect_systemd_container(root: &SysRoot) -> Result<Virtualization, Error> {
+    // systemd PID 1 might have dropped this information into a file in `/run`
+    // (always reachable through /var/run, which systemd links to it).
     // This is better than accessing `/proc/1/environ`,
     // since we don't need `CAP_SYS_PTRACE` for that.
-    let path = Path::new("/run/systemd/container");
+    let path = root.var_path("run/systemd/container");
     // If the path doesn't exist, no need to continue.
     // Doing so is faster than letting File::open fail (by 15%)
     // in case where the file doesn't exist.
     if !path.exists() {
         return Err(err_not_found());
     }
-    let file = File::open("/run/systemd/container")?;
+    let file = File::open(path)?;
     let mut file = BufReader::with_capacity(512, file);
     // Construct a String using 64 chars line capacity.
     let mut line = String::with_capacity(64);
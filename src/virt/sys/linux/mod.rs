@@ -1,4 +1,5 @@
 use crate::virt::Virtualization;
+use crate::{SysRoot, DEFAULT_ROOT};
 
 mod containers;
 mod vm_dmi;
@@ -7,9 +8,16 @@ mod vm_dmi;
 ///
 /// Return Unknown if it cannot determine the Virtualization used (if any).
 pub fn get_virt_info() -> Virtualization {
-    containers::detect_openvz()
-        .or_else(|_| containers::detect_wsl())
-        .or_else(|_| containers::detect_systemd_container())
-        .or_else(|_| vm_dmi::detect_vm_dmi())
+    get_virt_info_with_root(&DEFAULT_ROOT)
+}
+
+/// Get the virtualization information of the host reading from the given [SysRoot].
+///
+/// [SysRoot]: ../struct.SysRoot.html
+pub fn get_virt_info_with_root(root: &SysRoot) -> Virtualization {
+    containers::detect_openvz(root)
+        .or_else(|_| containers::detect_wsl(root))
+        .or_else(|_| containers::detect_systemd_container(root))
+        .or_else(|_| vm_dmi::detect_vm_dmi(root))
         .map_or(Virtualization::Unknown, |res| res)
 }
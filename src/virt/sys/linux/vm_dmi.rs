@@ -1,20 +1,18 @@
 use crate::virt::{err_not_found, Virtualization};
+use crate::{Error, SysRoot};
 
-use std::{
-    fs::File,
-    io::{Error, Read},
-};
+use std::{fs::File, io::Read};
 
-pub fn detect_vm_dmi() -> Result<Virtualization, Error> {
+pub fn detect_vm_dmi(root: &SysRoot) -> Result<Virtualization, Error> {
     let probing = [
-        "/sys/class/dmi/id/product_name",
-        "/sys/class/dmi/id/sys_vendor",
-        "/sys/class/dmi/id/board_vendor",
-        "/sys/class/dmi/id/bios_vendor",
+        "class/dmi/id/product_name",
+        "class/dmi/id/sys_vendor",
+        "class/dmi/id/board_vendor",
+        "class/dmi/id/bios_vendor",
     ];
 
     for path in probing {
-        let mut file = File::open(path)?;
+        let mut file = File::open(root.sys_path(path))?;
         let mut buf = [0u8; 3];
         file.read_exact(&mut buf)?;
 
@@ -8,10 +8,8 @@ mod macos;
 #[cfg(target_os = "macos")]
 pub use macos::*;
 
+/// Error of a probe which didn't detect its virtualization, get_virt_info then tries the next one.
 #[inline]
-pub(crate) fn err_not_found() -> std::io::Error {
-    std::io::Error::new(
-        std::io::ErrorKind::Other,
-        "Content of WSL's path doesn't match our criteria.",
-    )
+pub(crate) fn err_not_found() -> crate::Error {
+    crate::Error::Unsupported("virtualization")
 }
@@ -0,0 +1,212 @@
+#[cfg(target_os = "linux")]
+mod common;
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod cgroup {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use sys_metrics::cgroup::*;
+    use sys_metrics::pressure::PressureResource;
+    use sys_metrics::Error;
+
+    use crate::common::*;
+
+    const POD: &str =
+        "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod8f2c.slice/cri-containerd-5e1a.scope";
+
+    #[test]
+    fn test_current_cgroup() {
+        match get_current_cgroup() {
+            Ok(cgroup) => assert!(cgroup.path.starts_with('/')),
+            // Not running in a cgroup v2 hierarchy
+            Err(Error::Unsupported(_)) => {}
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn test_fixtures_cgroup_path() {
+        let path = get_cgroup_path_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(path, POD);
+
+        let path = parse_cgroup_path("1:cpu:/\n0::/system.slice/ssh.service\n".as_bytes());
+        assert_eq!(path.unwrap(), "/system.slice/ssh.service");
+        assert!(matches!(
+            parse_cgroup_path("4:memory:/docker/1234\n".as_bytes()),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_fixtures_cgroup() {
+        let root = fixture_root("linux-5.15");
+
+        let cgroup = get_current_cgroup_with_root(&root).unwrap();
+        assert_eq!(cgroup.path, POD);
+        assert_eq!(cgroup.cpu.usage_usec, 8735291032);
+        assert_eq!(cgroup.cpu.nr_throttled, Some(1207));
+        assert_eq!(
+            cgroup.cpu.max,
+            Some(CpuMax {
+                quota: Some(150000),
+                period: 100000
+            })
+        );
+
+        let memory = cgroup.memory.unwrap();
+        assert_eq!(memory.current, 402653184);
+        assert_eq!(memory.max, Some(536870912));
+        assert_eq!(memory.stat.anon, 301989888);
+        assert_eq!(memory.stat.slab, 9961472);
+        assert_eq!(memory.stat.pgmajfault, 412);
+
+        assert_eq!(cgroup.io.len(), 2);
+        assert_eq!((cgroup.io[0].major, cgroup.io[0].minor), (259, 0));
+        assert_eq!(cgroup.io[0].wbytes, 1348751360);
+        assert_eq!(cgroup.io[1].rios, 1);
+
+        assert_eq!(cgroup.pids, Some(37));
+
+        // No controller enabled
+        let cgroup = get_cgroup_with_root(&root, "/init.scope").unwrap();
+        assert_eq!(cgroup.cpu.system_usec, 510724);
+        assert_eq!(cgroup.cpu.nr_periods, None);
+        assert!(cgroup.cpu.max.is_none());
+        assert!(cgroup.memory.is_none());
+        assert!(cgroup.io.is_empty());
+        assert!(cgroup.pids.is_none());
+
+        // No cgroup hierarchy mounted
+        assert!(matches!(
+            get_cgroup_with_root(&fixture_root("linux-2.6.32"), "/"),
+            Err(Error::Unsupported(_))
+        ));
+        assert_eq!(parse_cgroup_cpu_max("max 100000").unwrap().quota, None);
+    }
+
+    #[test]
+    fn test_fixtures_cgroup_mode() {
+        assert!(matches!(
+            get_cgroup_mode_with_root(&fixture_root("linux-2.6.32")),
+            Err(Error::Unsupported(_))
+        ));
+        assert_eq!(
+            get_cgroup_mode_with_root(&fixture_root("linux-4.19")).unwrap(),
+            CgroupMode::Hybrid
+        );
+        assert_eq!(
+            get_cgroup_mode_with_root(&fixture_root("linux-5.15")).unwrap(),
+            CgroupMode::V2
+        );
+    }
+
+    #[test]
+    fn test_fixtures_cgroup_v1() {
+        let root = fixture_root("linux-4.19");
+        let ticks = sys_metrics::clock_ticks().unwrap();
+
+        let cgroup = get_current_cgroup_with_root(&root).unwrap();
+        assert_eq!(cgroup.path, "/system.slice/agent.service");
+        assert_eq!(cgroup.mode, CgroupMode::Hybrid);
+        assert_eq!(cgroup.cpu.usage_usec, 91827364);
+        assert_eq!(cgroup.cpu.user_usec, 6120 * 1_000_000 / ticks);
+        assert_eq!(cgroup.cpu.nr_throttled, Some(331));
+        assert_eq!(cgroup.cpu.throttled_usec, Some(18271823));
+        assert_eq!(
+            cgroup.cpu.max,
+            Some(CpuMax {
+                quota: Some(50000),
+                period: 100000
+            })
+        );
+
+        let memory = cgroup.memory.unwrap();
+        assert_eq!(memory.current, 148373504);
+        // PAGE_COUNTER_MAX means unlimited
+        assert_eq!(memory.max, None);
+        assert_eq!(memory.stat.anon, 98304000);
+        assert_eq!(memory.stat.file, 41775104);
+        assert_eq!(memory.stat.pgmajfault, 98);
+
+        assert_eq!(cgroup.io.len(), 1);
+        assert_eq!((cgroup.io[0].major, cgroup.io[0].minor), (179, 0));
+        assert_eq!(cgroup.io[0].rbytes, 35721216);
+        assert_eq!(cgroup.io[0].wios, 902);
+
+        assert_eq!(cgroup.pids, Some(12));
+
+        let cgroup = get_cgroup_with_root(&root, "/system.slice/agent.service").unwrap();
+        assert_eq!(cgroup.cpu.usage_usec, 91827364);
+    }
+
+    #[test]
+    fn test_fixtures_cgroup_v1_mounts() {
+        use std::fs;
+
+        // Copy the fixtures somewhere we can change the mounts and remove files
+        let dir = std::env::temp_dir().join(format!("sys_metrics-cgroup-{}", std::process::id()));
+        copy_fixture("linux-4.19", "proc/self", &dir);
+        copy_fixture("linux-4.19", "sys/fs/cgroup", &dir);
+
+        // The root of the memory mount is a prefix of the cgroup path but not its parent
+        let mountinfo = dir.join("proc/self/mountinfo");
+        let content = fs::read_to_string(&mountinfo).unwrap().replace(
+            "0:28 / /sys/fs/cgroup/memory",
+            "0:28 /system.slice/agent /sys/fs/cgroup/memory",
+        );
+        fs::write(&mountinfo, content).unwrap();
+        // No CONFIG_BLK_DEV_THROTTLING
+        let blkio = dir.join("sys/fs/cgroup/blkio/system.slice/agent.service");
+        fs::remove_file(blkio.join("blkio.throttle.io_service_bytes")).unwrap();
+        fs::remove_file(blkio.join("blkio.throttle.io_serviced")).unwrap();
+
+        let cgroup = get_current_cgroup_with_root(&sys_metrics::SysRoot::from_prefix(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let cgroup = cgroup.unwrap();
+        assert_eq!(cgroup.memory.unwrap().current, 148373504);
+        assert!(cgroup.io.is_empty());
+        assert_eq!(cgroup.pids, Some(12));
+    }
+
+    #[test]
+    fn test_fixtures_cgroup_cpu_limit() {
+        // The limit of the pod (1 cpu) is lower than the one of the container (1.5)
+        let limit = get_cgroup_cpu_limit_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(limit, Some(1.0));
+
+        let limit = get_cgroup_cpu_limit_with_root(&fixture_root("linux-4.19")).unwrap();
+        assert_eq!(limit, Some(0.5));
+    }
+
+    #[test]
+    fn test_fixtures_cgroup_memory_limit() {
+        let limit = get_cgroup_memory_limit_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(limit, Some(536870912));
+
+        // 9223372036854771712 means unlimited
+        let limit = get_cgroup_memory_limit_with_root(&fixture_root("linux-4.19")).unwrap();
+        assert_eq!(limit, None);
+    }
+
+    #[test]
+    fn test_fixtures_cgroup_pressure() {
+        let root = fixture_root("linux-5.15");
+
+        let cpu = get_current_cgroup_pressure_with_root(&root, PressureResource::Cpu).unwrap();
+        assert_eq!(cpu.some.avg10, 12.41);
+        assert_eq!(cpu.full.unwrap().total, 887120334);
+
+        let memory = get_cgroup_pressure_with_root(&root, POD, PressureResource::Memory).unwrap();
+        assert_eq!(memory.some.total, 118223);
+
+        // Only available in the cgroup v2 hierarchy
+        assert!(matches!(
+            get_current_cgroup_pressure_with_root(
+                &fixture_root("linux-4.19"),
+                PressureResource::Cpu
+            ),
+            Err(Error::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,47 @@
+// Helpers shared by the integration tests to read the captured
+// /proc and /sys samples located in tests/fixtures/<kernel>/.
+#![allow(dead_code)]
+
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use sys_metrics::SysRoot;
+
+/// Kernels for which a capture is available in tests/fixtures.
+pub const KERNELS: [&str; 3] = ["linux-2.6.32", "linux-4.19", "linux-5.15"];
+
+/// Return the path of the fixtures directory for `kernel`.
+pub fn fixture_dir(kernel: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(kernel)
+}
+
+/// Open the fixture `path` (eg: "proc/stat") captured on `kernel`.
+pub fn fixture(kernel: &str, path: &str) -> BufReader<File> {
+    let file = File::open(fixture_dir(kernel).join(path)).expect("Missing fixture");
+    BufReader::new(file)
+}
+
+/// Return a SysRoot pointing to the fixtures captured on `kernel`.
+pub fn fixture_root(kernel: &str) -> SysRoot {
+    SysRoot::from_prefix(fixture_dir(kernel))
+}
+
+/// Copy the fixtures `path` (eg: "sys/block") captured on `kernel` into `dest`,
+/// for the tests which need to modify them.
+pub fn copy_fixture(kernel: &str, path: &str, dest: &Path) {
+    fn copy_dir(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &to.join(entry.file_name()));
+            } else {
+                fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+            }
+        }
+    }
+
+    copy_dir(&fixture_dir(kernel).join(path), &dest.join(path));
+}
@@ -1,8 +1,14 @@
+#[cfg(target_os = "linux")]
+mod common;
+
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
 mod cpu {
     use sys_metrics::cpu::*;
 
+    #[cfg(target_os = "linux")]
+    use crate::common::*;
+
     #[test]
     fn test_cpucorecount() {
         let logical_count = get_logical_count().unwrap();
@@ -30,6 +36,179 @@ mod cpu {
         assert!(cputimes.idle_time() >= 0);
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cputimes_with_root() {
+        let root = sys_metrics::SysRoot::from_prefix("/");
+        assert_eq!(root, sys_metrics::SysRoot::default());
+
+        let cputimes = get_cputimes_with_root(&root).unwrap();
+        assert!(cputimes.total_time() > 0);
+
+        let root = sys_metrics::SysRoot::from_prefix("/nonexistent");
+        assert!(get_cputimes_with_root(&root).is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cputimes_percpu() {
+        let cputimes = get_cputimes_percpu().unwrap();
+
+        assert!(!cputimes.is_empty());
+        assert!(cputimes.len() >= get_logical_count().unwrap() as usize);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cpu_usage_sampler() {
+        let mut sampler = CpuUsageSampler::new().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        let sample = sampler.sample().unwrap();
+
+        assert_eq!(sample.percpu.len(), get_cputimes_percpu().unwrap().len());
+        if let Some(usage) = sample.total {
+            let sum = usage.busy() + usage.idle + usage.iowait;
+            assert!((sum - 100.0).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_cpu_usage() {
+        let prev = parse_cputimes(fixture("linux-5.15", "proc/stat")).unwrap();
+        let mut curr = prev.clone();
+        curr.user += 30;
+        curr.idle += 70;
+
+        let usage = CpuUsage::from_delta(&prev, &curr).unwrap();
+        assert_eq!(usage.user, 30.0);
+        assert_eq!(usage.idle, 70.0);
+        assert!(CpuUsage::from_delta(&prev, &prev).is_none());
+        assert!(CpuUsage::from_delta(&curr, &prev).is_none());
+
+        // Only iowait went backward
+        curr.iowait -= 10;
+        let usage = CpuUsage::from_delta(&prev, &curr).unwrap();
+        assert_eq!(usage.iowait, 0.0);
+        assert_eq!(usage.user, 30.0);
+        assert_eq!(usage.idle, 70.0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_effective_cpu_count() {
+        let count = get_effective_cpu_count().unwrap();
+
+        assert!(count >= 1);
+        assert!(count <= get_logical_count().unwrap());
+
+        // Limited to half a cpu, rounded up
+        assert_eq!(
+            get_effective_cpu_count_with_root(&fixture_root("linux-4.19")).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_cputimes() {
+        let cputimes = parse_cputimes(fixture("linux-2.6.32", "proc/stat")).unwrap();
+        assert_eq!(cputimes.user, 2255);
+        assert_eq!(cputimes.nice, 34);
+        assert_eq!(cputimes.system, 2290);
+        assert_eq!(cputimes.idle, 22625563);
+        assert_eq!(cputimes.iowait, 6290);
+        assert_eq!(cputimes.irq, 127);
+        assert_eq!(cputimes.softirq, 456);
+        // guest_nice does not exists on 2.6.32
+        assert_eq!(cputimes.guest_nice, 0);
+
+        let cputimes = parse_cputimes(fixture("linux-5.15", "proc/stat")).unwrap();
+        assert_eq!(cputimes.user, 10132153);
+        assert_eq!(cputimes.guest, 175628);
+        assert_eq!(cputimes.total_time(), 60377929);
+
+        for kernel in KERNELS {
+            let from_root = get_cputimes_with_root(&fixture_root(kernel)).unwrap();
+            let parsed = parse_cputimes(fixture(kernel, "proc/stat")).unwrap();
+            assert_eq!(from_root.total_time(), parsed.total_time());
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cputimes_errors() {
+        match parse_cputimes("intr 0\ncpu  1 2 x 4 5 6 7\n".as_bytes()) {
+            Err(sys_metrics::Error::Parse { file, line }) => {
+                assert_eq!(file, "/proc/stat");
+                assert_eq!(line, 2);
+            }
+            other => panic!("Expected a Parse error, got {:?}", other),
+        }
+
+        assert!(matches!(
+            parse_cputimes("cpu  1 2 3\n".as_bytes()),
+            Err(sys_metrics::Error::MissingField("idle"))
+        ));
+        assert!(matches!(
+            parse_cpustats("ctxt 1\nprocesses 2\n".as_bytes()),
+            Err(sys_metrics::Error::MissingField("intr"))
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_cputimes_percpu() {
+        let percpu = parse_cputimes_percpu(fixture("linux-2.6.32", "proc/stat")).unwrap();
+        assert_eq!(percpu.len(), 2);
+        assert_eq!(percpu[1].user, 1123);
+
+        let percpu = parse_cputimes_percpu(fixture("linux-4.19", "proc/stat")).unwrap();
+        assert_eq!(percpu.len(), 4);
+        assert_eq!(percpu[3].system, 91014);
+
+        // cpu2 is offline in this capture
+        let percpu = parse_cputimes_percpu(fixture("linux-5.15", "proc/stat")).unwrap();
+        assert_eq!(percpu.len(), 4);
+        assert_eq!(percpu[2].total_time(), 0);
+        assert_eq!(percpu[3].user, 1283417);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_cpustats() {
+        let cpustats = parse_cpustats(fixture("linux-2.6.32", "proc/stat")).unwrap();
+        assert_eq!(cpustats.interrupts, 114930548);
+        assert_eq!(cpustats.ctx_switches, 1990473);
+        assert_eq!(cpustats.soft_interrupts, 183433);
+        assert_eq!(cpustats.processes, 2915);
+        assert_eq!(cpustats.procs_running, 1);
+        assert_eq!(cpustats.procs_blocked, 0);
+
+        let cpustats = get_cpustats_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(cpustats.ctx_switches, 421284839);
+        assert_eq!(cpustats.procs_blocked, 1);
+
+        // A truncated file must not panic
+        assert!(parse_cpustats("cpu\n\nintr 1\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_cpufreq() {
+        let cpufreq = parse_cpufreq(fixture("linux-2.6.32", "proc/cpuinfo")).unwrap();
+        assert_eq!(cpufreq, 2400.084);
+
+        let cpufreq = get_cpufreq_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(cpufreq, 800.062);
+
+        // ARM's cpuinfo doesn't expose the frequency
+        assert!(parse_cpufreq(fixture("linux-4.19", "proc/cpuinfo")).is_err());
+
+        let physical = get_physical_count_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(physical, 4);
+    }
+
     #[test]
     fn test_cpustats() {
         let cpustats = get_cpustats().unwrap();
@@ -1,20 +1,402 @@
+#[cfg(target_os = "linux")]
+mod common;
+
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
 mod disks {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use sys_metrics::disks::*;
 
+    #[cfg(target_os = "linux")]
+    use crate::common::*;
+
     #[test]
     fn test_partitions_physical() {
         let partitions = get_partitions_physical().unwrap();
 
-        assert!(partitions.len() > 0);
+        assert!(!partitions.is_empty());
+    }
+
+    #[test]
+    fn test_partitions() {
+        for partition in get_partitions().unwrap() {
+            assert!(!partition.fs_type.is_empty());
+            assert!(partition.used_space <= partition.total_space);
+            assert!(partition.inodes_used <= partition.inodes_total);
+        }
+    }
+
+    #[test]
+    fn test_disk_usage() {
+        let usage = get_disk_usage("/").unwrap();
+        assert!(usage.total > 0);
+        assert!(usage.avail <= usage.total);
+        assert!(usage.used <= usage.total);
+        assert_eq!(usage.inodes_used, usage.inodes_total - usage.inodes_free);
+
+        assert_eq!(disk_usage("/").unwrap().0, usage.total);
+        assert!(get_disk_usage("/nonexistent/path").is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_ioblocks() {
+        let stats = parse_ioblocks(fixture("linux-2.6.32", "proc/diskstats")).unwrap();
+        assert_eq!(stats.len(), 7);
+        let sda = stats.iter().find(|s| s.device_name == "sda").unwrap();
+        assert_eq!(sda.read_count, 40212);
+        assert_eq!(sda.read_bytes, 2263862 * 512);
+        assert_eq!(sda.write_count, 52135);
+        assert_eq!(sda.write_bytes, 1794986 * 512);
+        assert_eq!(sda.read_time, 176478);
+        assert_eq!(sda.write_time, 1346284);
+        assert_eq!(sda.busy_time, 320372);
+        assert_eq!((sda.major, sda.minor), (8, 0));
+        assert_eq!(sda.read_merged, 13553);
+        assert_eq!(sda.write_merged, 171246);
+        assert_eq!(sda.in_flight, 0);
+        assert_eq!(sda.weighted_time, 1522612);
+        assert_eq!(sda.discard_count, None);
+        assert_eq!(sda.flush_count, None);
+
+        // 4.18+ adds the discard fields, 5.5+ the flush ones
+        let stats = parse_ioblocks(fixture("linux-4.19", "proc/diskstats")).unwrap();
+        assert_eq!(stats.len(), 5);
+        assert_eq!((stats[2].major, stats[2].minor), (179, 0));
+        assert_eq!(stats[2].discard_count, Some(12));
+        assert_eq!(stats[2].discard_bytes, Some(24 * 512));
+        assert_eq!(stats[2].discard_time, Some(0));
+        assert_eq!(stats[2].flush_count, None);
+        let stats = parse_ioblocks(fixture("linux-5.15", "proc/diskstats")).unwrap();
+        assert_eq!(stats.len(), 6);
+        assert_eq!(stats[2].device_name, "nvme0n1");
+        assert_eq!((stats[2].major, stats[2].minor), (259, 0));
+        assert_eq!(stats[2].write_count, 1002331);
+        assert_eq!(stats[2].busy_time, 653860);
+        assert_eq!(stats[2].discard_merged, Some(0));
+        assert_eq!(stats[2].discard_bytes, Some(171232792 * 512));
+        assert_eq!(stats[2].discard_time, Some(3104));
+        assert_eq!(stats[2].flush_count, Some(161209));
+        assert_eq!(stats[2].flush_time, Some(81648));
+
+        assert!(parse_ioblocks("   8       0 sda 1 2 3\n".as_bytes()).is_err());
+        assert!(parse_ioblocks("   8       0 sda 1 0 8 1 0 0 0 0 0 1 1 x\n".as_bytes()).is_err());
+
+        let expected = [
+            ("linux-2.6.32", "sda"),
+            ("linux-4.19", "mmcblk0"),
+            ("linux-5.15", "nvme0n1"),
+        ];
+        for (kernel, device) in expected {
+            let stats = get_physical_ioblocks_with_root(&fixture_root(kernel)).unwrap();
+            assert_eq!(stats.len(), 1);
+            assert_eq!(stats[0].device_name, device);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_mounts() {
+        let mounts = parse_mounts(fixture("linux-2.6.32", "proc/mounts")).unwrap();
+        assert_eq!(mounts.len(), 10);
+        let boot = mounts.iter().find(|m| m.mount_point == "/boot").unwrap();
+        assert_eq!(boot.name, "/dev/sda1");
+        assert_eq!(boot.fs_type, "ext4");
+        assert_eq!(
+            boot.options,
+            ["rw", "relatime", "barrier=1", "data=ordered"]
+        );
+
+        for kernel in KERNELS {
+            let mounts = parse_mounts(fixture(kernel, "proc/mounts")).unwrap();
+            assert!(mounts.iter().any(|m| m.mount_point == "/"));
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_ioblock_rate() {
+        use std::time::Duration;
+
+        let stats = parse_ioblocks(fixture("linux-5.15", "proc/diskstats")).unwrap();
+        let prev = stats.iter().find(|s| s.device_name == "nvme0n1").unwrap();
+        let mut curr = prev.clone();
+        curr.read_count += 200;
+        curr.read_bytes += 200 * 4096;
+        curr.read_time += 100;
+        curr.write_count += 50;
+        curr.write_bytes += 50 * 8192;
+        curr.write_time += 250;
+        curr.busy_time += 500;
+
+        let rate = IoBlockRate::from_delta(prev, &curr, Duration::from_secs(2)).unwrap();
+        assert_eq!(rate.device_name, "nvme0n1");
+        assert_eq!(rate.read_iops, 100.0);
+        assert_eq!(rate.write_iops, 25.0);
+        assert_eq!(rate.read_throughput, 409600.0);
+        assert_eq!(rate.write_throughput, 204800.0);
+        assert_eq!(rate.read_await, Some(0.5));
+        assert_eq!(rate.write_await, Some(5.0));
+        assert_eq!(rate.util, 25.0);
+
+        // No I/O at all
+        let rate = IoBlockRate::from_delta(prev, prev, Duration::from_secs(1)).unwrap();
+        assert_eq!(rate.read_iops, 0.0);
+        assert_eq!(rate.read_await, None);
+        assert_eq!(rate.util, 0.0);
+
+        // 32 bits counters wrapping around
+        let mut prev = prev.clone();
+        prev.read_count = u32::MAX as u64 - 9;
+        prev.read_bytes = (u32::MAX as u64 - 9) * 512;
+        curr.read_count = 10;
+        curr.read_bytes = 10 * 512;
+        let rate = IoBlockRate::from_delta(&prev, &curr, Duration::from_secs(1)).unwrap();
+        assert_eq!(rate.read_iops, 20.0);
+        assert_eq!(rate.read_throughput, 20.0 * 512.0);
+
+        // Counters reset (eg: the device was re-created under the same name)
+        prev.read_count = 1000;
+        prev.read_bytes = 1000 * 512;
+        assert!(IoBlockRate::from_delta(&prev, &curr, Duration::from_secs(1)).is_none());
+        prev.read_count = u32::MAX as u64 + 1000;
+        assert!(IoBlockRate::from_delta(&prev, &curr, Duration::from_secs(1)).is_none());
+        // Reset from the end of the 32 bits range to the middle of it
+        prev.read_count = u32::MAX as u64 - 9;
+        prev.read_bytes = (u32::MAX as u64 - 9) * 512;
+        curr.read_count = u32::MAX as u64 / 2;
+        curr.read_bytes = u32::MAX as u64 / 2 * 512;
+        assert!(IoBlockRate::from_delta(&prev, &curr, Duration::from_secs(1)).is_none());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_ioblock_sampler() {
+        use std::fs;
+
+        // Copy the fixtures somewhere we can make devices appear and disappear
+        let dir = std::env::temp_dir().join(format!("sys_metrics-ioblocks-{}", std::process::id()));
+        let diskstats = dir.join("proc/diskstats");
+        fs::create_dir_all(dir.join("proc")).unwrap();
+        let content = fs::read_to_string(fixture_dir("linux-5.15").join("proc/diskstats")).unwrap();
+        fs::write(&diskstats, &content).unwrap();
+
+        let mut sampler =
+            IoBlockSampler::with_root(sys_metrics::SysRoot::from_prefix(&dir)).unwrap();
+
+        // loop1 is detached, loop0 re-created and sdb plugged in
+        let content = content
+            .lines()
+            .filter(|line| !line.contains("loop1"))
+            .map(|line| match line.contains("loop0") {
+                true => "   7       0 loop0 5 0 40 1 0 0 0 0 0 2 1 0 0 0 0 0 0",
+                false => line,
+            })
+            .chain(std::iter::once(
+                "   8      16 sdb 10 0 80 4 0 0 0 0 0 4 4 0 0 0 0 0 0",
+            ))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(&diskstats, content).unwrap();
+
+        let sample = sampler.sample().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(sample.added, ["sdb"]);
+        assert_eq!(sample.removed, ["loop1"]);
+        assert_eq!(sample.reset, ["loop0"]);
+        assert_eq!(sample.ioblocks.len(), 4);
+        // Nothing changed
+        let nvme = sample
+            .ioblocks
+            .iter()
+            .find(|rate| rate.device_name == "nvme0n1")
+            .unwrap();
+        assert_eq!(nvme.read_iops, 0.0);
+        assert_eq!(nvme.util, 0.0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_block_devices() {
+        for device in get_block_devices().unwrap() {
+            if let (Some(logical), Some(physical)) =
+                (device.logical_sector_size, device.physical_sector_size)
+            {
+                assert!(logical >= 512);
+                assert!(physical >= logical);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_block_devices() {
+        let devices = get_block_devices_with_root(&fixture_root("linux-5.15")).unwrap();
+        let names: Vec<&str> = devices.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["loop0", "nvme0n1", "nvme0n1p1", "nvme0n1p2"]);
+
+        let nvme = &devices[1];
+        assert_eq!((nvme.major, nvme.minor), (259, 0));
+        assert_eq!(nvme.parent, None);
+        assert_eq!(nvme.size, 1000215216 * 512);
+        assert_eq!(nvme.rotational, Some(false));
+        assert_eq!(
+            nvme.model.as_deref(),
+            Some("Samsung SSD 970 EVO Plus 500GB")
+        );
+        assert_eq!(nvme.vendor, None);
+        assert_eq!(nvme.serial.as_deref(), Some("S4EVNX0N512345A"));
+        assert_eq!(nvme.scheduler, None);
+
+        let part = &devices[3];
+        assert_eq!((part.major, part.minor), (259, 2));
+        assert_eq!(part.parent.as_deref(), Some("nvme0n1"));
+        assert_eq!(part.size, 999163904 * 512);
+        assert_eq!(part.model, nvme.model);
+
+        let loop0 = &devices[0];
+        assert!(loop0.read_only);
+        assert_eq!(loop0.rotational, Some(true));
+        assert_eq!(loop0.scheduler.as_deref(), Some("mq-deadline"));
+
+        // Advanced format disk with 4K physical sectors
+        let devices = get_block_devices_with_root(&fixture_root("linux-2.6.32")).unwrap();
+        assert_eq!(devices.len(), 3);
+        assert_eq!(devices[0].logical_sector_size, Some(512));
+        assert_eq!(devices[0].physical_sector_size, Some(4096));
+        assert_eq!(devices[0].vendor.as_deref(), Some("ATA"));
+        assert_eq!(devices[0].scheduler.as_deref(), Some("cfq"));
+        assert_eq!(devices[2].parent.as_deref(), Some("sda"));
+        assert_eq!(devices[2].physical_sector_size, Some(4096));
+
+        // MMC devices only have a name
+        let devices = get_block_devices_with_root(&fixture_root("linux-4.19")).unwrap();
+        assert_eq!(devices[0].model.as_deref(), Some("SC32G"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_block_devices_removed() {
+        use std::fs;
+
+        // Copy the fixtures somewhere we can remove devices and attributes
+        let dir = std::env::temp_dir().join(format!("sys_metrics-block-{}", std::process::id()));
+        let block = dir.join("sys/block");
+        copy_fixture("linux-5.15", "sys/block", &dir);
+
+        // loop0 has no queue and nvme0n1p1 is removed while being read
+        fs::remove_dir_all(block.join("loop0/queue")).unwrap();
+        fs::remove_file(block.join("nvme0n1/nvme0n1p1/dev")).unwrap();
+
+        let devices = get_block_devices_with_root(&sys_metrics::SysRoot::from_prefix(&dir));
+
+        // nvme0n1 is removed while being read
+        fs::remove_file(block.join("nvme0n1/dev")).unwrap();
+        let removed = get_block_devices_with_root(&sys_metrics::SysRoot::from_prefix(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let devices = devices.unwrap();
+        let names: Vec<&str> = devices.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["loop0", "nvme0n1", "nvme0n1p2"]);
+        assert_eq!(devices[0].logical_sector_size, None);
+        assert_eq!(devices[0].rotational, None);
+        assert_eq!(devices[0].scheduler, None);
+
+        let names: Vec<String> = removed.unwrap().into_iter().map(|d| d.name).collect();
+        assert_eq!(names, ["loop0"]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_partitions_bind_mounts() {
+        use std::fs;
+
+        // The usage is read from the mount points, so they must exist on the host
+        let dir = std::env::temp_dir().join(format!("sys_metrics-mounts-{}", std::process::id()));
+        let mnt = dir.join("mnt");
+        for mount_point in ["root", "home", "data", "srv", "disk"] {
+            fs::create_dir_all(mnt.join(mount_point)).unwrap();
+        }
+        fs::create_dir_all(dir.join("proc/self")).unwrap();
+        let mnt = mnt.to_str().unwrap();
+        let mountinfo = [
+            // Two btrfs subvolumes of the same filesystem and a bind mount of the second one
+            format!("28 1 0:40 /@ {}/root rw,relatime shared:1 - btrfs /dev/sda2 rw,subvol=/@", mnt),
+            format!("29 28 0:40 /@home {}/home rw,relatime shared:2 - btrfs /dev/sda2 rw,subvol=/@home", mnt),
+            format!("30 28 0:40 /@home/user/data {}/data rw,relatime shared:2 - btrfs /dev/sda2 rw,subvol=/@home", mnt),
+            // A bind mount listed before the mount of the filesystem root
+            format!("31 28 8:17 /srv {}/srv rw,relatime shared:3 - ext4 /dev/sdb1 rw", mnt),
+            format!("32 28 8:17 / {}/disk rw,relatime shared:3 - ext4 /dev/sdb1 rw", mnt),
+        ];
+        fs::write(dir.join("proc/self/mountinfo"), mountinfo.join("\n")).unwrap();
+
+        let partitions = get_partitions_with_root(&sys_metrics::SysRoot::from_prefix(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let mount_points: Vec<String> = partitions
+            .unwrap()
+            .into_iter()
+            .map(|disk| disk.mount_point)
+            .collect();
+        assert_eq!(
+            mount_points,
+            [
+                format!("{}/root", mnt),
+                format!("{}/home", mnt),
+                format!("{}/disk", mnt)
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_mountinfo() {
+        let mounts = get_mountinfo().unwrap();
+        assert!(mounts.iter().any(|m| m.mount_point == "/"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_mountinfo() {
+        let mounts = get_mountinfo_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(mounts.len(), 10);
+
+        let root = mounts.iter().find(|m| m.mount_point == "/").unwrap();
+        assert_eq!((root.mount_id, root.parent_id), (28, 1));
+        assert_eq!((root.major, root.minor), (259, 2));
+        assert_eq!(root.root, "/");
+        assert_eq!(root.mount_options, ["rw", "relatime"]);
+        assert_eq!(root.propagation, ["shared:1"]);
+        assert_eq!(root.fs_type, "ext4");
+        assert_eq!(root.source, "/dev/nvme0n1p2");
+        assert_eq!(root.super_options, ["rw", "errors=remount-ro"]);
+
+        // Bind mount of a directory with a space in its mount point
+        let bind = mounts.iter().find(|m| m.mount_id == 52).unwrap();
+        assert_eq!(bind.root, "/var/lib/docker");
+        assert_eq!(bind.mount_point, "/mnt/docker data");
+        assert_eq!((bind.major, bind.minor), (root.major, root.minor));
+
+        let run_user = mounts.iter().find(|m| m.mount_id == 611).unwrap();
+        assert_eq!(run_user.propagation, ["shared:452", "master:5"]);
+
+        // No propagation flags for private mounts
+        let mounts = parse_mountinfo(fixture("linux-2.6.32", "proc/self/mountinfo")).unwrap();
+        assert_eq!(mounts.len(), 9);
+        assert!(mounts.iter().all(|m| m.propagation.is_empty()));
+
+        assert!(parse_mountinfo("20 1 8:2 / / rw,relatime\n".as_bytes()).is_err());
+        assert!(parse_mountinfo("20 1 8 / / rw - ext4 /dev/sda2 rw\n".as_bytes()).is_err());
+
+        let mounts = parse_mounts("/dev/sdb1 /media/My\\040Disk vfat rw 0 0\n".as_bytes()).unwrap();
+        assert_eq!(mounts[0].mount_point, "/media/My Disk");
     }
 
     #[test]
     fn test_physical_ioblocks() {
         let stats = get_physical_ioblocks().unwrap();
 
-        assert!(stats.len() > 0);
+        assert!(!stats.is_empty());
     }
 }
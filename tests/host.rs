@@ -1,10 +1,20 @@
+#[cfg(target_os = "linux")]
+mod common;
+
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(
+    unused_comparisons,
+    clippy::absurd_extreme_comparisons,
+    clippy::assertions_on_constants
+)]
 mod host {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use sys_metrics::host::*;
     use sys_metrics::virt::{self, *};
 
+    #[cfg(target_os = "linux")]
+    use crate::common::*;
+
     #[test]
     fn test_host_info() {
         let host = get_host_info().unwrap();
@@ -18,19 +28,19 @@ mod host {
         assert!(f >= 0.0);
 
         let b = host.system;
-        assert!(b.len() > 0);
+        assert!(!b.is_empty());
 
         let c = host.uptime;
         assert!(c > 0);
 
         let x = host.os_version;
-        assert!(x.len() > 0);
+        assert!(!x.is_empty());
 
         let y = host.kernel_version;
-        assert!(y.len() > 0);
+        assert!(!y.is_empty());
 
         let z = host.hostname;
-        assert!(z.len() > 0);
+        assert!(!z.is_empty());
     }
 
     #[test]
@@ -46,12 +56,48 @@ mod host {
         }
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_logged_users() {
+        use std::{fs, mem, slice};
+
+        let record = |ut_type: libc::c_short, user: &str| -> Vec<u8> {
+            let mut utmpx: libc::utmpx = unsafe { mem::zeroed() };
+            utmpx.ut_type = ut_type;
+            for (dst, src) in utmpx.ut_user.iter_mut().zip(user.bytes()) {
+                *dst = src as libc::c_char;
+            }
+            let bytes = unsafe {
+                slice::from_raw_parts(
+                    &utmpx as *const libc::utmpx as *const u8,
+                    mem::size_of::<libc::utmpx>(),
+                )
+            };
+            bytes.to_vec()
+        };
+
+        // A truncated utmp ending with half of a record
+        let mut content = record(libc::USER_PROCESS, "alice");
+        content.extend(record(libc::LOGIN_PROCESS, "LOGIN"));
+        content.extend(record(libc::USER_PROCESS, "bob"));
+        content.truncate(content.len() - mem::size_of::<libc::utmpx>() / 2);
+
+        let dir = std::env::temp_dir().join(format!("sys_metrics-utmp-{}", std::process::id()));
+        fs::create_dir_all(dir.join("var/run")).unwrap();
+        fs::write(dir.join("var/run/utmp"), content).unwrap();
+
+        let users = get_logged_users_with_root(&sys_metrics::SysRoot::from_prefix(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(users.unwrap(), ["alice"]);
+    }
+
     #[test]
     fn test_users() {
         let users = get_users().unwrap();
 
         #[cfg(target_os = "linux")]
-        assert!(users.len() > 0);
+        assert!(!users.is_empty());
         #[cfg(target_os = "macos")]
         #[allow(unused_comparisons)]
         assert!(users.len() >= 0);
@@ -63,10 +109,32 @@ mod host {
 
         if x != Virtualization::Wsl {
             let uuid = get_uuid().unwrap();
-            assert!(uuid.len() > 0);
+            assert!(!uuid.is_empty());
         } else {
             // On WSL assume this test as success
             assert!(true);
         }
     }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_file_handles() {
+        let file_handles = get_file_handles().unwrap();
+
+        assert!(file_handles.allocated > 0);
+        assert!(file_handles.allocated <= file_handles.max);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_file_handles() {
+        let file_handles = get_file_handles_with_root(&fixture_root("linux-2.6.32")).unwrap();
+        assert_eq!(file_handles.allocated, 3264);
+        assert_eq!(file_handles.unused, 0);
+        assert_eq!(file_handles.max, 787141);
+
+        let file_handles = get_file_handles_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(file_handles.allocated, 14592);
+        assert_eq!(file_handles.max, 9223372036854775807);
+    }
 }
@@ -1,18 +1,132 @@
+#[cfg(target_os = "linux")]
+mod common;
+
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(
+    unused_comparisons,
+    clippy::absurd_extreme_comparisons,
+    clippy::assertions_on_constants
+)]
 mod memory {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use sys_metrics::memory::*;
 
+    #[cfg(target_os = "linux")]
+    use crate::common::*;
+
     #[test]
     fn test_memory() {
         let mem = get_memory().unwrap();
 
         assert!(mem.total > 0);
         assert!(mem.free >= 0);
+        assert!(mem.available <= mem.total);
         assert!(mem.used >= 0);
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_memory() {
+        let mem = parse_memory(fixture("linux-2.6.32", "proc/meminfo")).unwrap();
+        assert_eq!(mem.total, 7872);
+        assert_eq!(mem.free, 1431);
+        assert_eq!(mem.buffers, 314);
+        // Cached + SReclaimable
+        assert_eq!(mem.cached, 4749);
+        assert_eq!(mem.shared, 21);
+        // No MemAvailable, estimated without the watermarks
+        assert_eq!(mem.available, 6474);
+        assert_eq!(mem.used, 1398);
+
+        // No MemAvailable, estimated using the watermarks of /proc/zoneinfo
+        let mem = get_memory_with_root(&fixture_root("linux-2.6.32")).unwrap();
+        if unsafe { libc::sysconf(libc::_SC_PAGESIZE) } == 4096 {
+            assert_eq!(mem.available, 6423);
+            assert_eq!(mem.used, 1449);
+        }
+
+        let mem = get_memory_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(mem.total, 15921);
+        assert_eq!(mem.available, 8421);
+        assert_eq!(mem.used, 7499);
+
+        for kernel in KERNELS {
+            let mem = parse_memory(fixture(kernel, "proc/meminfo")).unwrap();
+            assert!(mem.available <= mem.total);
+            assert!(mem.used + mem.available <= mem.total);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_meminfo() {
+        let meminfo = get_meminfo().unwrap();
+
+        assert!(meminfo.mem_total > 0);
+        assert!(meminfo.mem_free <= meminfo.mem_total);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_meminfo() {
+        let meminfo = parse_meminfo(fixture("linux-2.6.32", "proc/meminfo")).unwrap();
+        assert_eq!(meminfo.mem_total, 8061376 * 1024);
+        assert_eq!(meminfo.mem_available, None);
+        assert_eq!(meminfo.huge_pages_total, Some(0));
+        assert_eq!(meminfo.hugepagesize, Some(2048 * 1024));
+
+        let meminfo = parse_meminfo(fixture("linux-4.19", "proc/meminfo")).unwrap();
+        assert_eq!(meminfo.mem_available, Some(3373664 * 1024));
+        assert_eq!(meminfo.cma_total, Some(262144 * 1024));
+
+        let meminfo = get_meminfo_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(meminfo.mem_total, 16303428 * 1024);
+        assert_eq!(meminfo.mem_available, Some(8623512 * 1024));
+        assert_eq!(meminfo.cma_total, None);
+
+        assert!(parse_meminfo("Cached: 12 kB\n".as_bytes()).is_err());
+        assert!(parse_meminfo("MemTotal: abc kB\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_effective_memory() {
+        let mem = get_effective_memory().unwrap();
+
+        assert!(mem.total > 0);
+        assert!(mem.used + mem.available <= mem.total);
+        assert!(mem.total <= get_memory().unwrap().total);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_effective_memory() {
+        // Limited by the memory.max of the container, used is the working set
+        let mem = get_effective_memory_with_root(&fixture_root("linux-5.15")).unwrap();
+        assert_eq!(mem.source, MemorySource::Cgroup);
+        assert_eq!(mem.total, 512);
+        assert_eq!(mem.used, 336);
+        assert_eq!(mem.available, 176);
+
+        // The cgroup v1 memory controller has no limit
+        let mem = get_effective_memory_with_root(&fixture_root("linux-4.19")).unwrap();
+        assert_eq!(mem.source, MemorySource::MemInfo);
+        assert_eq!(mem.total, 3906);
+
+        // No cgroup at all
+        let mem = get_effective_memory_with_root(&fixture_root("linux-2.6.32")).unwrap();
+        assert_eq!(mem.source, MemorySource::MemInfo);
+        assert_eq!(mem.total, 7872);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_has_swap() {
+        assert!(has_swap_with_root(&fixture_root("linux-2.6.32")).unwrap());
+        assert!(has_swap_with_root(&fixture_root("linux-4.19")).unwrap());
+        assert!(!has_swap_with_root(&fixture_root("linux-5.15")).unwrap());
+    }
+
     #[test]
     fn test_has_swap() {
         let _ = has_swap().unwrap();
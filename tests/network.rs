@@ -1,9 +1,237 @@
+#[cfg(target_os = "linux")]
+mod common;
+
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
 mod network {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use sys_metrics::network::*;
 
+    #[cfg(target_os = "linux")]
+    use crate::common::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_ionets() {
+        let ionets = parse_ionets(fixture("linux-2.6.32", "proc/net/dev")).unwrap();
+        assert_eq!(ionets.len(), 2);
+        let eth0 = &ionets[1];
+        assert_eq!(eth0.interface, "eth0");
+        assert_eq!(eth0.rx_bytes, 3876843620);
+        assert_eq!(eth0.rx_packets, 3208754);
+        assert_eq!(eth0.rx_errs, 0);
+        assert_eq!(eth0.rx_drop, 3);
+        assert_eq!(eth0.tx_bytes, 189233641);
+        assert_eq!(eth0.tx_packets, 1601829);
+        assert_eq!(eth0.rx_multicast, 1720);
+        assert_eq!(eth0.tx_colls, 0);
+
+        let ionets = parse_ionets(fixture("linux-4.19", "proc/net/dev")).unwrap();
+        let eth0 = &ionets[1];
+        assert_eq!(eth0.rx_errs, 31);
+        assert_eq!(eth0.rx_fifo, 0);
+        assert_eq!(eth0.rx_frame, 27);
+        assert_eq!(eth0.rx_compressed, 0);
+        assert_eq!(eth0.rx_multicast, 71234);
+        assert_eq!(eth0.tx_bytes, 417284591);
+        assert_eq!(eth0.tx_carrier, 2);
+        assert_eq!(eth0.tx_compressed, 0);
+
+        let ionets = parse_ionets(fixture("linux-5.15", "proc/net/dev")).unwrap();
+        assert_eq!(ionets[2].interface, "enp3s0");
+        assert_eq!(ionets[2].tx_carrier, 1);
+
+        let expected: [(&str, &[&str]); 3] = [
+            ("linux-2.6.32", &["eth0"]),
+            ("linux-4.19", &["eth0", "wlan0"]),
+            ("linux-5.15", &["wlp2s0", "enp3s0", "wwan0"]),
+        ];
+        for (kernel, interfaces) in expected {
+            let ionets = get_physical_ionets_with_root(&fixture_root(kernel)).unwrap();
+            let names: Vec<&str> = ionets.iter().map(|i| i.interface.as_str()).collect();
+            assert_eq!(names, interfaces);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_ionet_rate() {
+        use std::time::Duration;
+
+        let ionets = parse_ionets(fixture("linux-5.15", "proc/net/dev")).unwrap();
+        let prev = &ionets[1];
+        let mut curr = prev.clone();
+        curr.rx_bytes += 3000;
+        curr.rx_packets += 30;
+        curr.rx_drop += 3;
+        curr.tx_bytes += 1500;
+        curr.tx_errs += 6;
+
+        let rate = IoNetRate::from_delta(prev, &curr, Duration::from_secs(3)).unwrap();
+        assert_eq!(rate.interface, "wlp2s0");
+        assert_eq!(rate.rx_bytes, 1000.0);
+        assert_eq!(rate.rx_packets, 10.0);
+        assert_eq!(rate.rx_drop, 1.0);
+        assert_eq!(rate.rx_errs, 0.0);
+        assert_eq!(rate.tx_bytes, 500.0);
+        assert_eq!(rate.tx_errs, 2.0);
+
+        // The counters were reset
+        assert!(IoNetRate::from_delta(&curr, prev, Duration::from_secs(3)).is_none());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_ionet_sampler() {
+        use std::fs;
+
+        // Copy the fixtures somewhere we can rename, remove and reset interfaces
+        let dir = std::env::temp_dir().join(format!("sys_metrics-ionets-{}", std::process::id()));
+        let dev = dir.join("proc/net/dev");
+        fs::create_dir_all(dir.join("proc/net")).unwrap();
+        let content = fs::read_to_string(fixture_dir("linux-5.15").join("proc/net/dev")).unwrap();
+        fs::write(&dev, &content).unwrap();
+        for (interface, ifindex) in [("lo", 1), ("wlp2s0", 2), ("enp3s0", 3), ("virbr0", 4)] {
+            let class = dir.join("sys/class/net").join(interface);
+            fs::create_dir_all(&class).unwrap();
+            fs::write(class.join("ifindex"), format!("{}\n", ifindex)).unwrap();
+        }
+
+        let mut sampler = IoNetSampler::with_root(sys_metrics::SysRoot::from_prefix(&dir)).unwrap();
+
+        // enp3s0 is renamed eth0, virbr0 is removed, wg0 is added and lo is reset
+        fs::rename(
+            dir.join("sys/class/net/enp3s0"),
+            dir.join("sys/class/net/eth0"),
+        )
+        .unwrap();
+        fs::remove_dir_all(dir.join("sys/class/net/virbr0")).unwrap();
+        let content = content
+            .lines()
+            .filter(|line| !line.contains("virbr0"))
+            .map(|line| match line.trim_start().split(':').next() {
+                Some("enp3s0") => line.replacen("enp3s0", "  eth0", 1),
+                Some("lo") => "    lo:     100       1    0    0    0     0          0         0      100       1    0    0    0     0       0          0".to_owned(),
+                _ => line.to_owned(),
+            })
+            .chain(std::iter::once(
+                "   wg0:       0       0    0    0    0     0          0         0        0       0    0    0    0     0       0          0".to_owned(),
+            ))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(&dev, content).unwrap();
+
+        let sample = sampler.sample().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(sample.added, ["wg0"]);
+        assert_eq!(sample.removed, ["virbr0"]);
+        assert_eq!(sample.renamed, [("enp3s0".to_owned(), "eth0".to_owned())]);
+        assert_eq!(sample.reset, ["lo"]);
+        let names: Vec<&str> = sample.ionets.iter().map(|i| i.interface.as_str()).collect();
+        assert_eq!(names, ["eth0", "wlp2s0", "wwan0"]);
+        assert_eq!(sample.ionets[1].rx_bytes, 0.0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_interfaces() {
+        let interfaces = get_interfaces().unwrap();
+        let lo = interfaces.iter().find(|i| i.name == "lo").unwrap();
+        assert!(lo.is_virtual);
+        assert!(lo
+            .addresses
+            .iter()
+            .any(|a| a.addr == std::net::Ipv4Addr::LOCALHOST && a.prefix_len == 8));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_interfaces() {
+        let interfaces = get_interfaces_with_root(&fixture_root("linux-5.15")).unwrap();
+        let kinds: Vec<(&str, InterfaceKind)> = interfaces
+            .iter()
+            .map(|i| (i.name.as_str(), i.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("lo", InterfaceKind::Loopback),
+                ("wlp2s0", InterfaceKind::Wireless),
+                ("enp3s0", InterfaceKind::Physical),
+                ("virbr0", InterfaceKind::Bridge),
+                ("bond0", InterfaceKind::Bond),
+                ("enp3s0.100", InterfaceKind::Vlan),
+                ("veth3f2a1c", InterfaceKind::Veth),
+                ("tun0", InterfaceKind::Tun),
+                ("tap0", InterfaceKind::Tap),
+                ("wg0", InterfaceKind::Wireguard),
+                ("macvlan0", InterfaceKind::Other),
+                ("wwan0", InterfaceKind::Physical),
+                ("gretap0", InterfaceKind::Other),
+            ]
+        );
+        assert_eq!(interfaces[7].mac, None);
+
+        let wlp2s0 = &interfaces[1];
+        assert_eq!(wlp2s0.index, 2);
+        assert_eq!(wlp2s0.mac.as_deref(), Some("3c:22:fb:4a:91:0e"));
+        assert_eq!(wlp2s0.mtu, 1500);
+        assert_eq!(wlp2s0.operstate, "up");
+        assert_eq!(wlp2s0.carrier, Some(true));
+        // Not reported by wireless drivers
+        assert_eq!(wlp2s0.speed, None);
+        assert!(!wlp2s0.is_virtual);
+
+        // Cable unplugged
+        let enp3s0 = &interfaces[2];
+        assert_eq!(enp3s0.carrier, Some(false));
+        assert_eq!(enp3s0.duplex, None);
+        assert_eq!(enp3s0.speed, None);
+
+        assert!(interfaces[0].is_virtual);
+        assert_eq!(interfaces[0].mtu, 65536);
+        assert!(interfaces[3].is_virtual);
+
+        let interfaces = get_interfaces_with_root(&fixture_root("linux-4.19")).unwrap();
+        assert_eq!(interfaces[3].kind, InterfaceKind::Bridge);
+        let eth0 = &interfaces[1];
+        assert_eq!(eth0.kind, InterfaceKind::Physical);
+        assert_eq!(eth0.name, "eth0");
+        assert_eq!(eth0.duplex.as_deref(), Some("full"));
+        assert_eq!(eth0.speed, Some(1000));
+        // Administratively down
+        assert_eq!(interfaces[2].name, "wlan0");
+        assert_eq!(interfaces[2].carrier, None);
+
+        let interfaces = get_interfaces_with_root(&fixture_root("linux-2.6.32")).unwrap();
+        assert_eq!(interfaces[0].kind, InterfaceKind::Loopback);
+        assert_eq!(interfaces[1].kind, InterfaceKind::Physical);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_fixtures_interfaces_removed() {
+        use std::fs;
+
+        // eth0 is removed between the read of its ifindex and of its mtu
+        let dir = std::env::temp_dir().join(format!("sys_metrics-ifaces-{}", std::process::id()));
+        for (interface, ifindex, mtu) in [("lo", 1, Some(65536)), ("eth0", 2, None)] {
+            let class = dir.join("sys/class/net").join(interface);
+            fs::create_dir_all(&class).unwrap();
+            fs::write(class.join("ifindex"), format!("{}\n", ifindex)).unwrap();
+            if let Some(mtu) = mtu {
+                fs::write(class.join("mtu"), format!("{}\n", mtu)).unwrap();
+            }
+        }
+
+        let interfaces = get_interfaces_with_root(&sys_metrics::SysRoot::from_prefix(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<String> = interfaces.unwrap().into_iter().map(|i| i.name).collect();
+        assert_eq!(names, ["lo"]);
+    }
+
     #[test]
     fn test_ionets() {
         #[cfg(target_os = "linux")]
@@ -11,6 +239,6 @@ mod network {
         #[cfg(target_os = "macos")]
         let ionets = get_ionets().unwrap();
 
-        assert!(ionets.len() > 0);
+        assert!(!ionets.is_empty());
     }
 }
@@ -0,0 +1,63 @@
+#[cfg(target_os = "linux")]
+mod common;
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod pressure {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use sys_metrics::pressure::*;
+    use sys_metrics::Error;
+
+    use crate::common::*;
+
+    #[test]
+    fn test_pressure() {
+        for resource in [
+            PressureResource::Cpu,
+            PressureResource::Memory,
+            PressureResource::Io,
+        ] {
+            match get_pressure(resource) {
+                Ok(pressure) => assert!(pressure.some.avg10 <= 100.0),
+                // Kernel without PSI
+                Err(Error::Unsupported(_)) => {}
+                Err(err) => panic!("{}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixtures_pressure() {
+        let root = fixture_root("linux-5.15");
+
+        let cpu = get_pressure_with_root(&root, PressureResource::Cpu).unwrap();
+        assert_eq!(cpu.some.avg10, 4.26);
+        assert_eq!(cpu.some.avg300, 3.29);
+        assert_eq!(cpu.some.total, 66077200);
+        assert_eq!(cpu.full.unwrap().total, 0);
+
+        let io = get_pressure_with_root(&root, PressureResource::Io).unwrap();
+        assert_eq!(io.some.avg60, 0.97);
+        assert_eq!(io.full.unwrap().avg10, 1.12);
+        assert_eq!(io.full.unwrap().total, 40182256);
+
+        // No PSI before 4.20
+        assert!(matches!(
+            get_pressure_with_root(&fixture_root("linux-4.19"), PressureResource::Memory),
+            Err(Error::Unsupported(_))
+        ));
+
+        // No full line for the cpu before 5.13
+        let cpu = parse_pressure("some avg10=0.50 avg60=0.25 avg300=0.10 total=1234\n".as_bytes())
+            .unwrap();
+        assert_eq!(cpu.some.avg10, 0.5);
+        assert_eq!(cpu.full, None);
+
+        assert!(
+            parse_pressure("full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n".as_bytes()).is_err()
+        );
+        assert!(
+            parse_pressure("some avg10=abc avg60=0.00 avg300=0.00 total=0\n".as_bytes()).is_err()
+        );
+    }
+}
@@ -0,0 +1,239 @@
+#[cfg(target_os = "linux")]
+mod common;
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod process {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use sys_metrics::process::*;
+
+    use crate::common::*;
+
+    #[test]
+    fn test_processes() {
+        let processes = get_processes().unwrap();
+
+        assert!(!processes.is_empty());
+        assert!(processes
+            .iter()
+            .any(|process| process.pid == std::process::id() as i32));
+    }
+
+    #[test]
+    fn test_process() {
+        let process = get_process(std::process::id() as i32).unwrap();
+
+        assert!(!process.name.is_empty());
+        assert!(!process.cmdline.is_empty());
+        assert!(process.threads >= 1);
+        assert!(process.rss > 0);
+        assert!(process.vsz >= process.rss);
+
+        assert!(get_process(i32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_fixtures_processes() {
+        let root = fixture_root("linux-5.15");
+        let ticks = sys_metrics::clock_ticks().unwrap();
+
+        let processes = get_processes_with_root(&root).unwrap();
+        let pids: Vec<i32> = processes.iter().map(|process| process.pid).collect();
+        assert_eq!(pids, [1, 2, 1842]);
+
+        let init = &processes[0];
+        assert_eq!(init.ppid, 0);
+        assert_eq!(init.name, "systemd");
+        assert_eq!(init.cmdline, ["/sbin/init", "splash"]);
+        assert_eq!(init.state, ProcessState::Sleeping);
+        assert_eq!((init.uid, init.gid), (0, 0));
+        assert_eq!(init.threads, 1);
+        assert_eq!(init.start_time, 1650873412 + 29 / ticks);
+        assert_eq!(init.start_ticks, 29);
+        assert_eq!(init.vsz, 172191744);
+        assert_eq!((init.utime, init.stime), (412, 1893));
+
+        // Kernel threads don't have any cmdline
+        assert!(processes[1].cmdline.is_empty());
+        assert_eq!(processes[1].rss, 0);
+
+        // The name may contain spaces and parenthesis
+        let content = get_process_with_root(&root, 1842).unwrap();
+        assert_eq!(content.name, "Web Content (x)");
+        assert_eq!(content.ppid, 1620);
+        assert_eq!(content.state, ProcessState::Running);
+        assert_eq!((content.uid, content.gid), (1000, 1000));
+        assert_eq!(content.threads, 27);
+        assert_eq!(content.start_time, 1650873412 + 418762 / ticks);
+        assert_eq!(content.cmdline.len(), 5);
+
+        assert!(get_process_with_root(&root, 3).is_err());
+    }
+
+    #[test]
+    fn test_fixtures_processes_denied() {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        // root can read the files whatever their permissions
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+
+        // Copy the fixtures somewhere we can make a process unreadable
+        let dir = std::env::temp_dir().join(format!("sys_metrics-denied-{}", std::process::id()));
+        let fixtures = fixture_dir("linux-5.15");
+        fs::create_dir_all(dir.join("proc")).unwrap();
+        fs::copy(fixtures.join("proc/stat"), dir.join("proc/stat")).unwrap();
+        for pid in ["1", "2", "1842"] {
+            fs::create_dir_all(dir.join("proc").join(pid)).unwrap();
+            for file in ["stat", "status", "cmdline"] {
+                let path = format!("proc/{}/{}", pid, file);
+                fs::copy(fixtures.join(&path), dir.join(&path)).unwrap();
+            }
+        }
+        let status = dir.join("proc/1842/status");
+        fs::set_permissions(&status, fs::Permissions::from_mode(0o000)).unwrap();
+
+        let root = sys_metrics::SysRoot::from_prefix(&dir);
+        let processes = get_processes_with_root(&root);
+        let process = get_process_with_root(&root, 1842);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let pids: Vec<i32> = processes.unwrap().iter().map(|p| p.pid).collect();
+        assert_eq!(pids, [1, 2]);
+        assert!(process.is_err());
+    }
+
+    #[test]
+    fn test_process_sampler() {
+        let mut sampler = ProcessSampler::new().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        let sample = sampler.sample().unwrap();
+
+        let myself = sample
+            .processes
+            .iter()
+            .find(|usage| usage.process.pid == std::process::id() as i32)
+            .unwrap();
+        let cpu_usage = myself.cpu_usage.unwrap();
+        assert!((0.0..=100.0).contains(&cpu_usage));
+        assert!(myself.rss_delta.is_some());
+    }
+
+    #[test]
+    fn test_fixtures_process_sampler() {
+        use std::fs;
+
+        // Copy the fixtures somewhere we can make processes appear and exit
+        let dir = std::env::temp_dir().join(format!("sys_metrics-sampler-{}", std::process::id()));
+        let proc_dir = dir.join("proc");
+        let fixtures = fixture_dir("linux-5.15").join("proc");
+        for pid in ["1", "2"] {
+            fs::create_dir_all(proc_dir.join(pid)).unwrap();
+            for file in ["stat", "status", "cmdline"] {
+                fs::copy(fixtures.join(pid).join(file), proc_dir.join(pid).join(file)).unwrap();
+            }
+        }
+        fs::copy(fixtures.join("stat"), proc_dir.join("stat")).unwrap();
+
+        let mut sampler =
+            ProcessSampler::with_root(sys_metrics::SysRoot::from_prefix(&dir)).unwrap();
+
+        // The boot time shifted by a second, pid 1 didn't change
+        let stat = fs::read_to_string(fixtures.join("stat")).unwrap();
+        fs::write(
+            proc_dir.join("stat"),
+            stat.replace("btime 1650873412", "btime 1650873413"),
+        )
+        .unwrap();
+        fs::remove_dir_all(proc_dir.join("2")).unwrap();
+        fs::create_dir_all(proc_dir.join("1842")).unwrap();
+        for file in ["stat", "status", "cmdline"] {
+            fs::copy(
+                fixtures.join("1842").join(file),
+                proc_dir.join("1842").join(file),
+            )
+            .unwrap();
+        }
+
+        let sample = sampler.sample().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(sample.appeared, [1842]);
+        assert_eq!(sample.exited, [2]);
+        assert_eq!(sample.processes.len(), 2);
+        // Nothing changed for pid 1
+        assert_eq!(sample.processes[0].cpu_usage, Some(0.0));
+        assert_eq!(sample.processes[0].rss_delta, Some(0));
+        assert!(sample.processes[1].cpu_usage.is_none());
+        assert!(sample.processes[1].rss_delta.is_none());
+    }
+
+    #[test]
+    fn test_process_io() {
+        let process_io = get_process_io(std::process::id() as i32).unwrap();
+
+        assert!(process_io.rchar > 0);
+        assert!(process_io.syscr > 0);
+    }
+
+    #[test]
+    fn test_fixtures_process_io() {
+        let root = fixture_root("linux-5.15");
+
+        let process_io = get_process_io_with_root(&root, 1842).unwrap();
+        assert_eq!(process_io.rchar, 2851473201);
+        assert_eq!(process_io.syscw, 318227);
+        assert_eq!(process_io.read_bytes, 77824000);
+        assert_eq!(process_io.write_bytes, 268935168);
+        assert_eq!(process_io.cancelled_write_bytes, 4096);
+
+        let mut curr = process_io.clone();
+        curr.read_bytes += 4096;
+        let delta = ProcessIo::from_delta(&process_io, &curr).unwrap();
+        assert_eq!(delta.read_bytes, 4096);
+        assert_eq!(delta.write_bytes, 0);
+        assert!(ProcessIo::from_delta(&curr, &process_io).is_none());
+
+        assert!(parse_process_io("rchar: 12\n".as_bytes()).is_err());
+        assert!(get_process_io_with_root(&root, 2).is_err());
+    }
+
+    #[test]
+    fn test_process_fds() {
+        let pid = std::process::id() as i32;
+        let limits = get_process_limits(pid).unwrap();
+
+        assert!(get_fd_count(pid).unwrap() >= 3);
+        assert!(
+            limits.open_files.soft <= limits.open_files.hard || limits.open_files.hard.is_none()
+        );
+    }
+
+    #[test]
+    fn test_fixtures_process_fds() {
+        let root = fixture_root("linux-5.15");
+
+        assert_eq!(get_fd_count_with_root(&root, 1842).unwrap(), 8);
+
+        let limits = get_process_limits_with_root(&root, 1842).unwrap();
+        assert_eq!(limits.open_files.soft, Some(1024));
+        assert_eq!(limits.open_files.hard, Some(524288));
+        assert_eq!(
+            limits.cpu_time,
+            Limit {
+                soft: None,
+                hard: None
+            }
+        );
+        assert_eq!(limits.stack_size.soft, Some(8388608));
+        assert_eq!(limits.stack_size.hard, None);
+        // No unit for the priorities
+        assert_eq!(limits.nice_priority.soft, Some(0));
+        assert_eq!(limits.realtime_timeout.hard, None);
+
+        assert!(
+            parse_process_limits("Max cpu time unlimited unlimited seconds\n".as_bytes()).is_err()
+        );
+    }
+}